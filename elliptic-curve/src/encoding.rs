@@ -0,0 +1,224 @@
+use crate::short_weierstras::{ECPoint, EllipticCurve, EllipticCurveError, EllipticCurveTrait};
+use field::field::{Field, FieldTrait};
+
+// SEC1 (https://www.secg.org/sec1-v2.pdf, section 2.3) point encoding, with every
+// coordinate written big-endian on ceil(log2(p) / 8) bytes:
+//
+// infinity      -> 0x00
+// compressed    -> 0x02 | (y mod 2), x
+// uncompressed  -> 0x04, x, y
+const INFINITY: u8 = 0x00;
+const COMPRESSED_EVEN: u8 = 0x02;
+const COMPRESSED_ODD: u8 = 0x03;
+const UNCOMPRESSED: u8 = 0x04;
+
+impl EllipticCurve {
+    /// Number of bytes used to encode one coordinate
+    pub fn coordinate_len(&self) -> usize {
+        let bits = usize::BITS - (self.a.modulus() - 1).leading_zeros();
+        (bits as usize).div_ceil(8).max(1)
+    }
+
+    /// Decodes a SEC1 encoded point and checks that it lies on this curve
+    pub fn point_from_bytes(&self, bytes: &[u8]) -> Result<ECPoint, EllipticCurveError> {
+        let invalid = || EllipticCurveError::InvalidEncoding(bytes.to_vec());
+        let len = self.coordinate_len();
+
+        let (tag, rest) = bytes.split_first().ok_or_else(invalid)?;
+        match (*tag, rest.len()) {
+            (INFINITY, 0) => Ok(EllipticCurve::zero(self)),
+            (COMPRESSED_EVEN | COMPRESSED_ODD, l) if l == len => {
+                let x = self.coordinate_from_bytes(rest).ok_or_else(invalid)?;
+
+                // y^2 = x^3 + ax + b, pick the root with the requested parity
+                let rhs = x * x * x + self.a * x + self.b;
+                let y = rhs.sqrt().ok_or_else(invalid)?;
+                // y = 0 is its own negation, there is no odd root
+                if y.value == 0 && *tag == COMPRESSED_ODD {
+                    return Err(invalid());
+                }
+                let y = if (y.value & 1) as u8 == tag & 1 {
+                    y
+                } else {
+                    y.zero() - y
+                };
+
+                Ok(self.ec_point(x, y))
+            }
+            (UNCOMPRESSED, l) if l == 2 * len => {
+                let x = self
+                    .coordinate_from_bytes(&rest[..len])
+                    .ok_or_else(invalid)?;
+                let y = self
+                    .coordinate_from_bytes(&rest[len..])
+                    .ok_or_else(invalid)?;
+
+                let point = self.ec_point(x, y);
+                if !self.is_on_curve(&point) {
+                    return Err(EllipticCurveError::InvalidPoint(point));
+                }
+
+                Ok(point)
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    // rejects non canonical coordinates, i.e. values >= p
+    fn coordinate_from_bytes(&self, bytes: &[u8]) -> Option<Field> {
+        let value = bytes.iter().try_fold(0usize, |acc, byte| {
+            acc.checked_mul(256)?.checked_add(*byte as usize)
+        })?;
+
+        if value >= self.a.modulus() {
+            return None;
+        }
+
+        Some(Field::new(value, self.a.modulus()))
+    }
+}
+
+impl ECPoint {
+    /// SEC1 encoding, 1 + len bytes when compressed and 1 + 2 * len otherwise
+    pub fn to_bytes(&self, compressed: bool) -> Vec<u8> {
+        if self.is_infinity {
+            return vec![INFINITY];
+        }
+
+        let len = self.curve.coordinate_len();
+        let mut bytes = vec![];
+
+        if compressed {
+            bytes.push(COMPRESSED_EVEN | (self.y.value & 1) as u8);
+            bytes.extend(coordinate_to_bytes(&self.x, len));
+        } else {
+            bytes.push(UNCOMPRESSED);
+            bytes.extend(coordinate_to_bytes(&self.x, len));
+            bytes.extend(coordinate_to_bytes(&self.y, len));
+        }
+
+        bytes
+    }
+}
+
+fn coordinate_to_bytes(value: &Field, len: usize) -> Vec<u8> {
+    (0..len)
+        .rev()
+        .map(|i| (value.value >> (8 * i)) as u8)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a few points of y^2 = x^3 + ax + b mod p, found by taking square roots
+    fn sample_points(curve: &EllipticCurve, count: usize) -> Vec<ECPoint> {
+        let p = curve.a.modulus();
+        (0..p)
+            .filter_map(|x| {
+                let x = Field::new(x, p);
+                let y = (x * x * x + curve.a * x + curve.b).sqrt()?;
+                Some(curve.ec_point(x, y))
+            })
+            .take(count)
+            .collect()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let curves = [
+            EllipticCurve::new(Field::new(2, 17), Field::new(2, 17)),
+            EllipticCurve::new(Field::new(2, 97), Field::new(3, 97)),
+            EllipticCurve::new(Field::new(0, 65521), Field::new(7, 65521)),
+            EllipticCurve::new(Field::new(3, 1000003), Field::new(5, 1000003)),
+        ];
+
+        for (curve, len) in curves.iter().zip([1, 1, 2, 3]) {
+            assert_eq!(curve.coordinate_len(), len);
+
+            for point in sample_points(curve, 40) {
+                let compressed = point.to_bytes(true);
+                let uncompressed = point.to_bytes(false);
+                assert_eq!(compressed.len(), 1 + len);
+                assert_eq!(uncompressed.len(), 1 + 2 * len);
+
+                assert_eq!(curve.point_from_bytes(&compressed).unwrap(), point);
+                assert_eq!(curve.point_from_bytes(&uncompressed).unwrap(), point);
+
+                // the negated point only differs in the parity bit
+                let neg = curve.ec_point(point.x, point.y.zero() - point.y);
+                if point.y.value != 0 {
+                    assert_eq!(neg.to_bytes(true)[0], compressed[0] ^ 1);
+                    assert_eq!(curve.point_from_bytes(&neg.to_bytes(true)).unwrap(), neg);
+                }
+            }
+
+            let infinity = EllipticCurve::zero(curve);
+            assert_eq!(infinity.to_bytes(true), vec![0x00]);
+            assert_eq!(infinity.to_bytes(false), vec![0x00]);
+            assert_eq!(curve.point_from_bytes(&[0x00]).unwrap(), infinity);
+        }
+    }
+
+    #[test]
+    fn test_known_encoding() {
+        // y^2 = x^3 + 2x + 2 mod 17
+        let curve = EllipticCurve::new(Field::new(2, 17), Field::new(2, 17));
+        let point = curve.ec_point(Field::new(5, 17), Field::new(1, 17));
+
+        assert_eq!(point.to_bytes(true), vec![0x03, 0x05]);
+        assert_eq!(point.to_bytes(false), vec![0x04, 0x05, 0x01]);
+
+        let curve = EllipticCurve::new(Field::new(0, 65521), Field::new(7, 65521));
+        let point = sample_points(&curve, 1)[0];
+        let bytes = point.to_bytes(false);
+        assert_eq!(
+            bytes[1..3],
+            [(point.x.value >> 8) as u8, point.x.value as u8]
+        );
+    }
+
+    #[test]
+    fn test_invalid_encodings() {
+        let curve = EllipticCurve::new(Field::new(2, 17), Field::new(2, 17));
+
+        let invalid = [
+            vec![],
+            vec![0x00, 0x00],
+            vec![0x05, 0x05],
+            // wrong lengths
+            vec![0x02],
+            vec![0x02, 0x05, 0x01],
+            vec![0x04, 0x05],
+            // x >= p
+            vec![0x02, 0x11],
+            vec![0x04, 0x05, 0x11],
+            // x^3 + 2x + 2 = 4^3 + 10 = 6 mod 17 is not a square
+            vec![0x02, 0x04],
+        ];
+        for bytes in invalid {
+            assert!(matches!(
+                curve.point_from_bytes(&bytes),
+                Err(EllipticCurveError::InvalidEncoding(_))
+            ));
+        }
+
+        // well formed, but (4, 1) is not on the curve
+        assert!(matches!(
+            curve.point_from_bytes(&[0x04, 0x04, 0x01]),
+            Err(EllipticCurveError::InvalidPoint(_))
+        ));
+
+        // (1, 0) is on y^2 = x^3 + 2x + 14, but its y is even
+        let curve = EllipticCurve::new(Field::new(2, 17), Field::new(14, 17));
+        assert_eq!(
+            curve.point_from_bytes(&[0x02, 0x01]).unwrap(),
+            curve.ec_point(Field::new(1, 17), Field::new(0, 17))
+        );
+        assert!(matches!(
+            curve.point_from_bytes(&[0x03, 0x01]),
+            Err(EllipticCurveError::InvalidEncoding(_))
+        ));
+    }
+}
@@ -0,0 +1,136 @@
+use crate::short_weierstras::{ECPoint, EllipticCurve, EllipticCurveTrait};
+use field::field::{Field, FieldTrait};
+use sha2::{Digest, Sha256};
+
+// Try-and-increment hashing for toy curves:
+//
+// x = SHA-256(len(dst) || dst || counter || msg) mod p
+//
+// for counter = 0, 1, ... until x^3 + ax + b is a square, with the parity of y taken
+// from the same digest. Each attempt succeeds with probability about 1/2, and the
+// number of attempts depends on the message, so this is neither constant time nor
+// the indifferentiable construction of RFC 9380; it is fine for teaching and tests.
+impl EllipticCurve {
+    /// Deterministically maps `msg` to a point of the curve, `dst` separates domains
+    pub fn hash_to_curve(&self, dst: &[u8], msg: &[u8]) -> ECPoint {
+        self.try_and_increment(dst, msg, |point| Some(*point))
+    }
+
+    /// Like `hash_to_curve`, followed by cofactor clearing, skipping candidates that
+    /// land on the point at infinity
+    pub fn hash_to_subgroup(&self, dst: &[u8], msg: &[u8]) -> ECPoint {
+        let cofactor = self.prime_subgroup().cofactor;
+
+        self.try_and_increment(dst, msg, |point| {
+            let cleared = self
+                .montgomery_ladder(point, &[cofactor as u64])
+                .expect("Point should be on the curve");
+            (!cleared.is_infinity).then_some(cleared)
+        })
+    }
+
+    fn try_and_increment<M>(&self, dst: &[u8], msg: &[u8], accept: M) -> ECPoint
+    where
+        M: Fn(&ECPoint) -> Option<ECPoint>,
+    {
+        assert!(
+            dst.len() < 256,
+            "Domain separation tag should be shorter than 256 bytes"
+        );
+        let p = self.a.modulus();
+
+        for counter in 0..=u32::MAX {
+            let digest = Sha256::new()
+                .chain_update([dst.len() as u8])
+                .chain_update(dst)
+                .chain_update(counter.to_be_bytes())
+                .chain_update(msg)
+                .finalize();
+
+            let x = Field::new(reduce(&digest, p), p);
+            let Some(y) = (x * x * x + self.a * x + self.b).sqrt() else {
+                continue;
+            };
+
+            // pick one of the two roots with the first bit of the digest
+            let y = if (y.value & 1) as u8 == digest[0] & 1 {
+                y
+            } else {
+                y.zero() - y
+            };
+
+            if let Some(point) = accept(&self.ec_point(x, y)) {
+                return point;
+            }
+        }
+
+        unreachable!("Every non singular curve has points")
+    }
+}
+
+// big-endian bytes mod p
+fn reduce(bytes: &[u8], modulus: usize) -> usize {
+    bytes.iter().fold(0, |acc, byte| {
+        ((acc as u128 * 256 + *byte as u128) % modulus as u128) as usize
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DST: &[u8] = b"TOY-CURVE-TRY-AND-INCREMENT";
+
+    #[test]
+    fn test_hash_to_curve() {
+        let curves = [
+            EllipticCurve::new(Field::new(2, 17), Field::new(2, 17)),
+            EllipticCurve::new(Field::new(0, 65521), Field::new(7, 65521)),
+            EllipticCurve::new(Field::new(3, 1000003), Field::new(5, 1000003)),
+        ];
+
+        for curve in curves {
+            for msg in [&b""[..], b"abc", b"hello world", &[0xff; 100]] {
+                let point = curve.hash_to_curve(DST, msg);
+                assert!(curve.is_on_curve(&point));
+                assert!(!point.is_infinity);
+
+                // deterministic
+                assert_eq!(curve.hash_to_curve(DST, msg), point);
+            }
+        }
+    }
+
+    #[test]
+    fn test_domain_separation() {
+        let curve = EllipticCurve::new(Field::new(3, 1000003), Field::new(5, 1000003));
+
+        let a = curve.hash_to_curve(b"DOMAIN-A", b"message");
+        let b = curve.hash_to_curve(b"DOMAIN-B", b"message");
+        assert_ne!(a, b);
+
+        // (dst, msg) is framed by the length of dst, so moving bytes between them matters
+        let c = curve.hash_to_curve(b"DOMAIN-Am", b"essage");
+        assert_ne!(a, c);
+
+        let hashes: Vec<ECPoint> = (0..50u32)
+            .map(|i| curve.hash_to_curve(DST, &i.to_be_bytes()))
+            .collect();
+        for (i, a) in hashes.iter().enumerate() {
+            assert!(hashes[i + 1..].iter().all(|b| a != b));
+        }
+    }
+
+    #[test]
+    fn test_hash_to_subgroup() {
+        // y^2 = x^3 + x mod 10007 has 10008 = 72 * 139 points
+        let curve = EllipticCurve::new(Field::new(1, 10007), Field::new(0, 10007));
+        let subgroup = curve.prime_subgroup();
+
+        for i in 0..20u32 {
+            let point = curve.hash_to_subgroup(DST, &i.to_be_bytes());
+            assert!(!point.is_infinity);
+            assert!(subgroup.contains(&point).unwrap());
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use crate::short_weierstras::{CurveField, ECPoint, EllipticCurve};
+use crate::utils::double;
+use field::field::Field;
+
+// Jacobian coordinates: (X, Y, Z) represents the affine point (X / Z^2, Y / Z^3)
+// and the curve equation becomes Y^2 = X^3 + aXZ^4 + bZ^6.
+// The point at infinity is any point with Z = 0, canonically (1, 1, 0).
+#[derive(Debug, Clone, Copy)]
+pub struct JacobianPoint<F: CurveField = Field> {
+    pub x: F,
+    pub y: F,
+    pub z: F,
+    pub curve: EllipticCurve<F>,
+}
+
+impl<F: CurveField> JacobianPoint<F> {
+    pub fn new(x: F, y: F, z: F, curve: EllipticCurve<F>) -> Self {
+        Self { x, y, z, curve }
+    }
+
+    pub fn infinity(curve: &EllipticCurve<F>) -> Self {
+        let one = curve.a.one();
+        Self::new(one, one, curve.a.zero(), *curve)
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        self.z == self.z.zero()
+    }
+
+    pub fn from_affine(point: &ECPoint<F>) -> Self {
+        if point.is_infinity {
+            return Self::infinity(&point.curve);
+        }
+        Self::new(point.x, point.y, point.x.one(), point.curve)
+    }
+
+    /// (X, Y, Z) -> (X / Z^2, Y / Z^3), costs one field inversion
+    pub fn to_affine(&self) -> ECPoint<F> {
+        if self.is_infinity() {
+            return EllipticCurve::zero(&self.curve);
+        }
+
+        let z_inv = self.z.inverse().expect("Z should be invertible");
+        self.scale_to_affine(z_inv)
+    }
+
+    fn scale_to_affine(&self, z_inv: F) -> ECPoint<F> {
+        let z_inv_2 = z_inv * z_inv;
+        let z_inv_3 = z_inv_2 * z_inv;
+        ECPoint::new(self.x * z_inv_2, self.y * z_inv_3, self.curve)
+    }
+
+    // https://hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html#doubling-dbl-2007-bl
+    //
+    // No branch is needed: Z3 = 2YZ, so the point at infinity (Z = 0) and the points of
+    // order 2 (Y = 0) both double to Z3 = 0.
+    pub fn double(&self) -> Self {
+        let xx = self.x * self.x;
+        let yy = self.y * self.y;
+        let yyyy = yy * yy;
+        let zz = self.z * self.z;
+
+        // S = 2 * ((X + YY)^2 - XX - YYYY)
+        let x_plus_yy = self.x + yy;
+        let s = double(x_plus_yy * x_plus_yy - xx - yyyy);
+
+        // M = 3 * XX + a * ZZ^2
+        let m = double(xx) + xx + self.curve.a * zz * zz;
+
+        // X3 = M^2 - 2 * S
+        let x3 = m * m - double(s);
+
+        // Y3 = M * (S - X3) - 8 * YYYY
+        let y3 = m * (s - x3) - double(double(double(yyyy)));
+
+        // Z3 = (Y + Z)^2 - YY - ZZ
+        let y_plus_z = self.y + self.z;
+        let z3 = y_plus_z * y_plus_z - yy - zz;
+
+        Self::new(x3, y3, z3, self.curve)
+    }
+
+    pub fn add(&self, other: &JacobianPoint<F>) -> Self {
+        if self.is_infinity() {
+            return *other;
+        }
+        if other.is_infinity() {
+            return *self;
+        }
+
+        let (sum, h, r) = self.add_formula(other);
+
+        // same x coordinate: either the same point or inverses of each other
+        if h == h.zero() {
+            if r == r.zero() {
+                return self.double();
+            }
+            return Self::infinity(&self.curve);
+        }
+
+        sum
+    }
+
+    /// Same result as `add`, but every case is computed and the right one selected,
+    /// so the sequence of operations does not depend on the points
+    pub fn add_branch_free(&self, other: &JacobianPoint<F>) -> Self {
+        let (sum, h, r) = self.add_formula(other);
+
+        // h = 0 already gives Z3 = 0, which is only wrong when the points are equal
+        let same_point = (h == h.zero()) & (r == r.zero());
+        let sum = Self::conditional_select(&sum, &self.double(), same_point);
+
+        let sum = Self::conditional_select(&sum, other, self.is_infinity());
+        Self::conditional_select(&sum, self, other.is_infinity())
+    }
+
+    // https://hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html#addition-add-2007-bl
+    // the sum of two distinct finite points with H != 0, along with H and r
+    fn add_formula(&self, other: &JacobianPoint<F>) -> (Self, F, F) {
+        let z1z1 = self.z * self.z;
+        let z2z2 = other.z * other.z;
+        let u1 = self.x * z2z2;
+        let u2 = other.x * z1z1;
+        let s1 = self.y * other.z * z2z2;
+        let s2 = other.y * self.z * z1z1;
+
+        let h = u2 - u1;
+        let r = double(s2 - s1);
+
+        // I = (2 * H)^2, J = H * I, V = U1 * I
+        let i = double(h) * double(h);
+        let j = h * i;
+        let v = u1 * i;
+
+        // X3 = r^2 - J - 2 * V
+        let x3 = r * r - j - double(v);
+
+        // Y3 = r * (V - X3) - 2 * S1 * J
+        let y3 = r * (v - x3) - double(s1 * j);
+
+        // Z3 = ((Z1 + Z2)^2 - Z1Z1 - Z2Z2) * H
+        let z1_plus_z2 = self.z + other.z;
+        let z3 = (z1_plus_z2 * z1_plus_z2 - z1z1 - z2z2) * h;
+
+        (Self::new(x3, y3, z3, self.curve), h, r)
+    }
+
+    pub fn neg(&self) -> Self {
+        Self::new(self.x, self.y.zero() - self.y, self.z, self.curve)
+    }
+
+    /// `b` when `choice` is set and `a` otherwise, without branching on it:
+    /// with s = 0 or 1, every coordinate is a + s (b - a)
+    pub fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+        let s = [a.x.zero(), a.x.one()][choice as usize];
+        let select = |left: F, right: F| left + s * (right - left);
+
+        Self::new(
+            select(a.x, b.x),
+            select(a.y, b.y),
+            select(a.z, b.z),
+            a.curve,
+        )
+    }
+
+    /// Swaps `a` and `b` when `swap` is set, without branching on it
+    pub fn conditional_swap(a: &mut Self, b: &mut Self, swap: bool) {
+        let (new_a, new_b) = (
+            Self::conditional_select(a, b, swap),
+            Self::conditional_select(b, a, swap),
+        );
+        *a = new_a;
+        *b = new_b;
+    }
+}
+
+impl JacobianPoint {
+    /// Converts many points with a single field inversion (Montgomery's trick)
+    pub fn batch_to_affine(points: &[JacobianPoint]) -> Vec<ECPoint> {
+        let zs: Vec<Field> = points.iter().map(|point| point.z).collect();
+
+        Field::batch_inverse(&zs)
+            .into_iter()
+            .zip(points.iter())
+            .map(|(z_inv, point)| match z_inv {
+                Some(z_inv) => point.scale_to_affine(z_inv),
+                None => EllipticCurve::zero(&point.curve),
+            })
+            .collect()
+    }
+}
+
+impl<F: CurveField> From<ECPoint<F>> for JacobianPoint<F> {
+    fn from(point: ECPoint<F>) -> Self {
+        Self::from_affine(&point)
+    }
+}
+
+impl<F: CurveField> From<JacobianPoint<F>> for ECPoint<F> {
+    fn from(point: JacobianPoint<F>) -> Self {
+        point.to_affine()
+    }
+}
+
+// (X1, Y1, Z1) == (X2, Y2, Z2) iff X1 Z2^2 == X2 Z1^2 and Y1 Z2^3 == Y2 Z1^3
+impl<F: CurveField> PartialEq for JacobianPoint<F> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.is_infinity() || other.is_infinity() {
+            return self.is_infinity() == other.is_infinity();
+        }
+
+        let z1z1 = self.z * self.z;
+        let z2z2 = other.z * other.z;
+
+        self.x * z2z2 == other.x * z1z1 && self.y * z2z2 * other.z == other.y * z1z1 * self.z
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short_weierstras::EllipticCurveTrait;
+    use field::field::FieldTrait;
+
+    fn setup_curve() -> EllipticCurve {
+        // y^2 = x^3 + 2x + 2 mod 17, a cyclic group of order 19
+        EllipticCurve::new(Field::new(2, 17), Field::new(2, 17))
+    }
+
+    fn all_points(curve: &EllipticCurve) -> Vec<ECPoint> {
+        let p = curve.a.modulus();
+        let mut points = vec![EllipticCurve::zero(curve)];
+        for x in 0..p {
+            for y in 0..p {
+                let point = curve.ec_point(Field::new(x, p), Field::new(y, p));
+                if curve.is_on_curve(&point) {
+                    points.push(point);
+                }
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn test_affine_round_trip() {
+        let curve = setup_curve();
+        for point in all_points(&curve) {
+            let jacobian = JacobianPoint::from_affine(&point);
+            assert_eq!(jacobian.to_affine(), point);
+
+            // same point, different representative: (X t^2, Y t^3, t)
+            let t = Field::new(5, 17);
+            let scaled = JacobianPoint::new(point.x * t * t, point.y * t * t * t, t, curve);
+            if !point.is_infinity {
+                assert_eq!(scaled, jacobian);
+                assert_eq!(ECPoint::from(scaled), point);
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_and_double_match_affine() {
+        let curve = setup_curve();
+        let points = all_points(&curve);
+
+        for p in points.iter() {
+            let p_jacobian = JacobianPoint::from(*p);
+            assert_eq!(p_jacobian.double().to_affine(), curve.double(p).unwrap());
+
+            for q in points.iter() {
+                // the affine formula can't add a point to its inverse
+                if !p.is_infinity && !q.is_infinity && p.x == q.x && p.y != q.y {
+                    assert!(p_jacobian.add(&JacobianPoint::from(*q)).is_infinity());
+                    continue;
+                }
+
+                let sum = p_jacobian.add(&JacobianPoint::from(*q));
+                assert_eq!(sum.to_affine(), curve.add(p, q).unwrap());
+                assert_eq!(p_jacobian.add_branch_free(&JacobianPoint::from(*q)), sum);
+            }
+
+            assert!(p_jacobian.add(&p_jacobian.neg()).is_infinity());
+            assert!(p_jacobian.add_branch_free(&p_jacobian.neg()).is_infinity());
+        }
+    }
+
+    #[test]
+    fn test_batch_to_affine() {
+        let curve = setup_curve();
+        let generator = JacobianPoint::from(curve.ec_point(Field::new(5, 17), Field::new(1, 17)));
+
+        let mut multiples = vec![JacobianPoint::infinity(&curve)];
+        for _ in 0..25 {
+            let next = multiples[multiples.len() - 1].add(&generator);
+            multiples.push(next);
+        }
+
+        let expected: Vec<ECPoint> = multiples.iter().map(|point| point.to_affine()).collect();
+        assert_eq!(JacobianPoint::batch_to_affine(&multiples), expected);
+
+        // the group has order 19
+        assert!(multiples[19].is_infinity());
+        assert_eq!(multiples[20], generator);
+    }
+}
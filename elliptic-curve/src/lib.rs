@@ -1,2 +1,11 @@
+pub mod encoding;
+pub mod hash_to_curve;
+pub mod jacobian;
+pub mod montgomery;
+pub mod order;
+pub mod pairing;
+pub mod projective;
+pub mod scalar_multiplication;
 pub mod short_weierstras;
+pub mod twisted_edwards;
 pub mod utils;
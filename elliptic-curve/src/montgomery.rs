@@ -0,0 +1,354 @@
+use crate::short_weierstras::{ECPoint, EllipticCurve};
+use crate::utils::limb_bit;
+use field::field::{Field, FieldTrait};
+
+// A Montgomery curve is denoted by the equation
+// By^2 = x^3 + Ax^2 + x
+// with B != 0 and A^2 != 4. Curve25519 is A = 486662, B = 1 over 2^255 - 19.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MontgomeryCurve {
+    pub a: Field,
+    pub b: Field,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MontgomeryPoint {
+    pub x: Field,
+    pub y: Field,
+    pub curve: MontgomeryCurve,
+    pub is_infinity: bool,
+}
+
+impl MontgomeryCurve {
+    pub fn new(a: Field, b: Field) -> Self {
+        assert_ne!(b.value, 0, "B should be non zero");
+        assert_ne!(
+            (a * a).value,
+            4 % a.modulus(),
+            "A^2 should be different from 4"
+        );
+        Self { a, b }
+    }
+
+    pub fn point(&self, x: Field, y: Field) -> MontgomeryPoint {
+        MontgomeryPoint {
+            x,
+            y,
+            curve: *self,
+            is_infinity: false,
+        }
+    }
+
+    pub fn infinity(&self) -> MontgomeryPoint {
+        MontgomeryPoint {
+            x: self.a.zero(),
+            y: self.a.zero(),
+            curve: *self,
+            is_infinity: true,
+        }
+    }
+
+    pub fn is_on_curve(&self, point: &MontgomeryPoint) -> bool {
+        if point.is_infinity {
+            return true;
+        }
+
+        let x = point.x;
+        self.b * point.y * point.y == x * x * x + self.a * x * x + x
+    }
+
+    pub fn neg(&self, point: &MontgomeryPoint) -> MontgomeryPoint {
+        if point.is_infinity {
+            return *point;
+        }
+        self.point(point.x, point.y.zero() - point.y)
+    }
+
+    //     (y2 - y1)
+    // λ = ---------
+    //     (x2 - x1)
+    //
+    // x3 = Bλ^2 - A - x1 - x2
+    // y3 = λ(x1 - x3) - y1
+    pub fn add(&self, point_a: &MontgomeryPoint, point_b: &MontgomeryPoint) -> MontgomeryPoint {
+        if point_a.is_infinity {
+            return *point_b;
+        }
+        if point_b.is_infinity {
+            return *point_a;
+        }
+
+        if point_a.x == point_b.x {
+            if point_a.y == point_b.y {
+                return self.double(point_a);
+            }
+            return self.infinity();
+        }
+
+        let slope = (point_b.y - point_a.y) / (point_b.x - point_a.x);
+        let x3 = self.b * slope * slope - self.a - point_a.x - point_b.x;
+        let y3 = slope * (point_a.x - x3) - point_a.y;
+
+        self.point(x3, y3)
+    }
+
+    //     (3x1^2 + 2Ax1 + 1)
+    // λ = ------------------
+    //          (2By1)
+    //
+    // x3 = Bλ^2 - A - 2x1
+    pub fn double(&self, point: &MontgomeryPoint) -> MontgomeryPoint {
+        if point.is_infinity || point.y.value == 0 {
+            return self.infinity();
+        }
+
+        let x = point.x;
+        let three_x2 = x * x + x * x + x * x;
+        let numerator = three_x2 + self.a * x + self.a * x + x.one();
+        let slope = numerator / (self.b * point.y + self.b * point.y);
+
+        let x3 = self.b * slope * slope - self.a - x - x;
+        let y3 = slope * (x - x3) - point.y;
+
+        self.point(x3, y3)
+    }
+
+    /// Affine double-and-add over a little-endian limb scalar
+    pub fn scalar_multiplication(
+        &self,
+        point: &MontgomeryPoint,
+        scalar: &[u64],
+    ) -> MontgomeryPoint {
+        let mut result = self.infinity();
+        for i in (0..scalar.len() * 64).rev() {
+            result = self.double(&result);
+            if limb_bit(scalar, i) {
+                result = self.add(&result, point);
+            }
+        }
+        result
+    }
+
+    /// x-only Montgomery ladder (RFC 7748, section 5): the x coordinate of k * P
+    /// from the x coordinate of P alone, in (X : Z) projective coordinates.
+    /// The point at infinity is returned as 0, like X25519 does.
+    ///
+    /// Any x works, including ones of points on the quadratic twist.
+    pub fn ladder(&self, x: Field, scalar: &[u64]) -> Field {
+        let one = x.one();
+        let four = one + one + one + one;
+        let a24 = (self.a - one - one) / four;
+
+        let (mut x2, mut z2) = (one, x.zero());
+        let (mut x3, mut z3) = (x, one);
+        let mut swap = false;
+
+        for i in (0..scalar.len() * 64).rev() {
+            let bit = limb_bit(scalar, i);
+            swap ^= bit;
+            conditional_swap(&mut x2, &mut x3, swap);
+            conditional_swap(&mut z2, &mut z3, swap);
+            swap = bit;
+
+            let a = x2 + z2;
+            let aa = a * a;
+            let b = x2 - z2;
+            let bb = b * b;
+            let e = aa - bb;
+            let c = x3 + z3;
+            let d = x3 - z3;
+            let da = d * a;
+            let cb = c * b;
+
+            x3 = (da + cb) * (da + cb);
+            z3 = x * (da - cb) * (da - cb);
+            x2 = aa * bb;
+            z2 = e * (aa + a24 * e);
+        }
+        conditional_swap(&mut x2, &mut x3, swap);
+        conditional_swap(&mut z2, &mut z3, swap);
+
+        match z2.inverse() {
+            Some(z_inv) => x2 * z_inv,
+            None => x.zero(),
+        }
+    }
+
+    /// The birationally equivalent short Weierstrass curve
+    /// a = (3 - A^2) / 3B^2, b = (2A^3 - 9A) / 27B^3
+    pub fn to_weierstrass(&self) -> EllipticCurve {
+        let three = Field::new(3, self.a.modulus());
+        let a2 = self.a * self.a;
+
+        let a = (three - a2) / (three * self.b * self.b);
+        let b = (self.a * a2 + self.a * a2 - three * three * self.a)
+            / (three * three * three * self.b * self.b * self.b);
+
+        EllipticCurve::new(a, b)
+    }
+
+    /// (x, y) -> (x / B + A / 3B, y / B)
+    pub fn point_to_weierstrass(&self, point: &MontgomeryPoint) -> ECPoint {
+        let curve = self.to_weierstrass();
+        if point.is_infinity {
+            return EllipticCurve::zero(&curve);
+        }
+
+        let three = Field::new(3, self.a.modulus());
+        let x = point.x / self.b + self.a / (three * self.b);
+        let y = point.y / self.b;
+
+        ECPoint::new(x, y, curve)
+    }
+
+    /// (x, y) -> (Bx - A / 3, By)
+    pub fn point_from_weierstrass(&self, point: &ECPoint) -> MontgomeryPoint {
+        if point.is_infinity {
+            return self.infinity();
+        }
+
+        let three = Field::new(3, self.a.modulus());
+        self.point(self.b * point.x - self.a / three, self.b * point.y)
+    }
+
+    /// A Montgomery form of y^2 = x^3 + ax + b, which exists iff the cubic has a
+    /// root α and 3α^2 + a is a square. Then with s = 1 / sqrt(3α^2 + a),
+    /// A = 3αs and B = s. The root is found by exhaustive search, toy moduli only.
+    pub fn from_weierstrass(curve: &EllipticCurve) -> Option<Self> {
+        let p = curve.a.modulus();
+        let three = Field::new(3, p);
+
+        (0..p).map(|x| Field::new(x, p)).find_map(|alpha| {
+            if (alpha * alpha * alpha + curve.a * alpha + curve.b).value != 0 {
+                return None;
+            }
+
+            let s = (three * alpha * alpha + curve.a).sqrt()?.inverse()?;
+            Some(Self::new(three * alpha * s, s))
+        })
+    }
+}
+
+fn conditional_swap(a: &mut Field, b: &mut Field, swap: bool) {
+    let mask = (swap as usize).wrapping_neg();
+    let t = mask & (a.value ^ b.value);
+    a.value ^= t;
+    b.value ^= t;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short_weierstras::EllipticCurveTrait;
+
+    fn setup_curve() -> MontgomeryCurve {
+        // y^2 = x^3 + 6x^2 + x mod 1009
+        MontgomeryCurve::new(Field::new(6, 1009), Field::new(1, 1009))
+    }
+
+    fn sample_points(curve: &MontgomeryCurve, count: usize) -> Vec<MontgomeryPoint> {
+        let p = curve.a.modulus();
+        (0..p)
+            .filter_map(|x| {
+                let x = Field::new(x, p);
+                let y = ((x * x * x + curve.a * x * x + x) / curve.b).sqrt()?;
+                Some(curve.point(x, y))
+            })
+            .take(count)
+            .collect()
+    }
+
+    #[test]
+    fn test_group_law() {
+        let curve = setup_curve();
+        let points = sample_points(&curve, 20);
+
+        for p in points.iter() {
+            assert!(curve.is_on_curve(p));
+            assert!(curve.is_on_curve(&curve.double(p)));
+            assert!(curve.add(p, &curve.neg(p)).is_infinity);
+
+            for q in points.iter() {
+                let sum = curve.add(p, q);
+                assert!(curve.is_on_curve(&sum));
+                assert_eq!(sum, curve.add(q, p));
+            }
+        }
+
+        // (0, 0) has order 2
+        let zero = curve.point(Field::new(0, 1009), Field::new(0, 1009));
+        assert!(curve.is_on_curve(&zero));
+        assert!(curve.double(&zero).is_infinity);
+    }
+
+    #[test]
+    fn test_weierstrass_map_is_a_homomorphism() {
+        let curve = setup_curve();
+        let weierstrass = curve.to_weierstrass();
+        let points = sample_points(&curve, 20);
+
+        for p in points.iter() {
+            let p_w = curve.point_to_weierstrass(p);
+            assert!(weierstrass.is_on_curve(&p_w));
+            assert_eq!(curve.point_from_weierstrass(&p_w), *p);
+
+            for q in points.iter() {
+                let q_w = curve.point_to_weierstrass(q);
+                // the affine Weierstrass formulas can't handle P + (-P) or 2 * (x, 0)
+                if p.x == q.x {
+                    continue;
+                }
+                let sum = weierstrass.add(&p_w, &q_w).unwrap();
+                assert_eq!(curve.point_to_weierstrass(&curve.add(p, q)), sum);
+            }
+        }
+
+        // and back again, from a Weierstrass curve with the root x = 0
+        let curve = MontgomeryCurve::from_weierstrass(&weierstrass).unwrap();
+        assert_eq!(curve.to_weierstrass(), weierstrass);
+
+        // y^2 = x^3 + 2x + 2 mod 17 has prime order 19, so no point of order 2 and no root
+        let prime_order = EllipticCurve::new(Field::new(2, 17), Field::new(2, 17));
+        assert!(MontgomeryCurve::from_weierstrass(&prime_order).is_none());
+    }
+
+    #[test]
+    fn test_ladder() {
+        let curve = setup_curve();
+
+        for point in sample_points(&curve, 10) {
+            for k in 0..40u64 {
+                let expected = curve.scalar_multiplication(&point, &[k]);
+                let x = if expected.is_infinity {
+                    Field::new(0, 1009)
+                } else {
+                    expected.x
+                };
+                assert_eq!(curve.ladder(point.x, &[k]), x);
+            }
+
+            // multi-limb scalars agree with the affine double-and-add
+            let scalar = [0x0123_4567_89ab_cdef, 0xfedc_ba98];
+            let expected = curve.scalar_multiplication(&point, &scalar);
+            if !expected.is_infinity {
+                assert_eq!(curve.ladder(point.x, &scalar), expected.x);
+            }
+        }
+    }
+
+    #[test]
+    fn test_diffie_hellman() {
+        // X25519 style: only x coordinates are exchanged
+        let curve = setup_curve();
+        let base = sample_points(&curve, 5)[4].x;
+
+        let (alice, bob) = ([123u64], [457u64]);
+        let alice_public = curve.ladder(base, &alice);
+        let bob_public = curve.ladder(base, &bob);
+
+        assert_eq!(
+            curve.ladder(bob_public, &alice),
+            curve.ladder(alice_public, &bob)
+        );
+    }
+}
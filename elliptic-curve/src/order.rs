@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+
+use crate::jacobian::JacobianPoint;
+use crate::short_weierstras::{ECPoint, EllipticCurve, EllipticCurveError, EllipticCurveTrait};
+use field::field::{Field, FieldTrait};
+use field::utils::prime_factors;
+
+// Group order of E(F_p) for toy moduli. Hasse's theorem bounds it:
+//
+// | #E(F_p) - (p + 1) | <= 2 sqrt(p)
+//
+// so the order of any point P has a multiple in that interval, which baby-step
+// giant-step finds in O(p^(1/4)) group operations. Once the least common multiple
+// of a few point orders has a single multiple in the interval, that is #E(F_p).
+const MAX_POINTS: usize = 32;
+
+/// #E(F_p) = h * q, with q the largest prime factor of the group order.
+/// Built once by `EllipticCurve::prime_subgroup`, so the order is not recounted
+/// for every point that gets checked or cleared.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrimeSubgroup {
+    pub curve: EllipticCurve,
+    pub order: usize,
+    pub subgroup_order: usize,
+    pub cofactor: usize,
+}
+
+impl PrimeSubgroup {
+    /// h * P, which always lands in the subgroup of order q
+    pub fn clear_cofactor(&self, point: &ECPoint) -> Result<ECPoint, EllipticCurveError> {
+        self.curve.montgomery_ladder(point, &[self.cofactor as u64])
+    }
+
+    /// Whether q * P = O. When q^2 divides #E(F_p) this is the whole q-torsion,
+    /// which need not be a single cyclic subgroup.
+    pub fn contains(&self, point: &ECPoint) -> Result<bool, EllipticCurveError> {
+        let result = self
+            .curve
+            .montgomery_ladder(point, &[self.subgroup_order as u64])?;
+        Ok(result.is_infinity)
+    }
+
+    /// Accepts a scalar usable as a secret key for the subgroup, i.e. 0 < k < q
+    pub fn validate_scalar(&self, scalar: usize) -> Result<usize, EllipticCurveError> {
+        if scalar == 0 || scalar >= self.subgroup_order {
+            return Err(EllipticCurveError::InvalidScalar(scalar));
+        }
+
+        Ok(scalar)
+    }
+}
+
+impl EllipticCurve {
+    /// #E(F_p) = p + 1 + sum over x of the Legendre symbol (x^3 + ax + b | p)
+    pub fn order_naive(&self) -> usize {
+        let p = self.a.modulus();
+
+        // the formula needs p odd, over F_2 the points are counted directly
+        if p == 2 {
+            let affine = (0..p)
+                .flat_map(|x| (0..p).map(move |y| (Field::new(x, p), Field::new(y, p))))
+                .filter(|(x, y)| *y * *y == self.rhs(*x))
+                .count();
+            return affine + 1;
+        }
+
+        let sum: i64 = (0..p)
+            .map(|x| self.rhs(Field::new(x, p)).legendre_symbol() as i64)
+            .sum();
+
+        (p as i64 + 1 + sum) as usize
+    }
+
+    /// #E(F_p) with baby-step giant-step, falls back to `order_naive` if the point
+    /// orders never pin down a single candidate (e.g. very small p)
+    pub fn order(&self) -> usize {
+        let (low, high) = self.hasse_interval();
+        let mut exponent = 1;
+
+        for point in self.points().take(MAX_POINTS) {
+            let multiple = self.hasse_multiple(&point);
+            let order = self.reduce_to_order(&point, multiple);
+            exponent = lcm(exponent, order);
+
+            // multiples of the exponent inside [low, high]
+            let first = low.div_ceil(exponent) * exponent;
+            if first <= high && first + exponent > high {
+                return first;
+            }
+        }
+
+        self.order_naive()
+    }
+
+    /// The smallest n > 0 with n * P = O
+    pub fn order_of_point(&self, point: &ECPoint) -> Result<usize, EllipticCurveError> {
+        if !self.is_on_curve(point) {
+            return Err(EllipticCurveError::InvalidPoint(*point));
+        }
+
+        Ok(self.reduce_to_order(point, self.order()))
+    }
+
+    /// The largest prime order subgroup: #E(F_p), its largest prime factor q and the
+    /// cofactor h = #E(F_p) / q, with a single order computation
+    pub fn prime_subgroup(&self) -> PrimeSubgroup {
+        let order = self.order();
+        let subgroup_order = *prime_factors(order)
+            .last()
+            .expect("The group order is at least 1");
+
+        PrimeSubgroup {
+            curve: *self,
+            order,
+            subgroup_order,
+            cofactor: order / subgroup_order,
+        }
+    }
+
+    fn rhs(&self, x: Field) -> Field {
+        x * x * x + self.a * x + self.b
+    }
+
+    // [p + 1 - 2 sqrt(p), p + 1 + 2 sqrt(p)]
+    fn hasse_interval(&self) -> (usize, usize) {
+        let p = self.a.modulus();
+        let width = (4 * p).isqrt();
+        (p + 1 - width, p + 1 + width)
+    }
+
+    // affine points, one per x coordinate that has one
+    fn points(&self) -> impl Iterator<Item = ECPoint> + '_ {
+        let p = self.a.modulus();
+        (0..p).filter_map(move |x| {
+            let x = Field::new(x, p);
+            let y = self.rhs(x).sqrt()?;
+            Some(self.ec_point(x, y))
+        })
+    }
+
+    // some M in the Hasse interval with M * P = O:
+    // baby steps j P for 0 <= j < m, giant steps (low + i m) P, then j P = -(low + i m) P
+    fn hasse_multiple(&self, point: &ECPoint) -> usize {
+        let (low, high) = self.hasse_interval();
+        let m = (high - low).isqrt() + 1;
+
+        let base = JacobianPoint::from_affine(point);
+        let mut baby_steps = HashMap::new();
+        let mut current = JacobianPoint::infinity(self);
+        for j in 0..m {
+            baby_steps.entry(key(&current.to_affine())).or_insert(j);
+            current = current.add(&base);
+        }
+
+        let giant_step = current;
+        let mut current = JacobianPoint::from_affine(&self.multiply(point, low));
+        let mut offset = low;
+        while offset <= high {
+            if let Some(j) = baby_steps.get(&key(&current.neg().to_affine())) {
+                if offset + j <= high {
+                    return offset + j;
+                }
+            }
+            current = current.add(&giant_step);
+            offset += m;
+        }
+
+        unreachable!("Hasse's theorem guarantees a multiple of the point order in the interval")
+    }
+
+    // strips prime factors q from a multiple M of ord(P) while (M / q) P = O
+    fn reduce_to_order(&self, point: &ECPoint, multiple: usize) -> usize {
+        let mut order = multiple;
+        for q in prime_factors(multiple) {
+            while order.is_multiple_of(q) && self.multiply(point, order / q).is_infinity {
+                order /= q;
+            }
+        }
+        order
+    }
+
+    fn multiply(&self, point: &ECPoint, scalar: usize) -> ECPoint {
+        self.montgomery_ladder(point, &[scalar as u64])
+            .expect("Point should be on the curve")
+    }
+}
+
+fn key(point: &ECPoint) -> (bool, usize, usize) {
+    (point.is_infinity, point.x.value, point.y.value)
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    a / gcd(a, b) * b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_singular(curve: &EllipticCurve) -> bool {
+        let p = curve.a.modulus();
+        let discriminant = Field::new(4, p) * curve.a.pow(3) + Field::new(27, p) * curve.b.pow(2);
+        discriminant.value == 0
+    }
+
+    #[test]
+    fn test_order() {
+        // y^2 = x^3 + 2x + 2 mod 17
+        let curve = EllipticCurve::new(Field::new(2, 17), Field::new(2, 17));
+        assert_eq!(curve.order_naive(), 19);
+        assert_eq!(curve.order(), 19);
+        assert_eq!(curve.prime_subgroup().cofactor, 1);
+
+        // squaring is a bijection of F_2, so every x has exactly one y
+        for (a, b) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+            let curve = EllipticCurve::new(Field::new(a, 2), Field::new(b, 2));
+            assert_eq!(curve.order_naive(), 3);
+            assert_eq!(curve.order(), 3);
+        }
+
+        // supersingular: p = 3 mod 4 => #E = p + 1
+        let curve = EllipticCurve::new(Field::new(1, 10007), Field::new(0, 10007));
+        assert_eq!(curve.order(), 10008);
+    }
+
+    #[test]
+    fn test_order_matches_naive_count() {
+        for p in [23, 101] {
+            for a in 0..p {
+                for b in 0..p {
+                    let curve = EllipticCurve::new(Field::new(a, p), Field::new(b, p));
+                    if is_singular(&curve) {
+                        continue;
+                    }
+                    assert_eq!(curve.order(), curve.order_naive(), "a = {a}, b = {b}");
+                }
+            }
+        }
+
+        for (a, b) in [(2, 3), (0, 7), (5, 11), (65000, 17)] {
+            let curve = EllipticCurve::new(Field::new(a, 65521), Field::new(b, 65521));
+            assert_eq!(curve.order(), curve.order_naive());
+        }
+    }
+
+    #[test]
+    fn test_order_of_point() {
+        let curve = EllipticCurve::new(Field::new(2, 17), Field::new(2, 17));
+        for point in curve.points() {
+            assert_eq!(curve.order_of_point(&point).unwrap(), 19);
+        }
+        let infinity = EllipticCurve::zero(&curve);
+        assert_eq!(curve.order_of_point(&infinity).unwrap(), 1);
+
+        let invalid = curve.ec_point(Field::new(4, 17), Field::new(1, 17));
+        assert!(curve.order_of_point(&invalid).is_err());
+
+        // y^2 = x^3 + x mod 23 has 24 points, (0, 0) has order 2
+        let curve = EllipticCurve::new(Field::new(1, 23), Field::new(0, 23));
+        let point = curve.ec_point(Field::new(0, 23), Field::new(0, 23));
+        assert_eq!(curve.order_of_point(&point).unwrap(), 2);
+        for point in curve.points() {
+            let order = curve.order_of_point(&point).unwrap();
+            assert!(24 % order == 0);
+            assert!(curve.multiply(&point, order).is_infinity);
+        }
+    }
+
+    #[test]
+    fn test_cofactor_and_subgroup() {
+        // #E = 24 = 8 * 3
+        let curve = EllipticCurve::new(Field::new(1, 23), Field::new(0, 23));
+        let subgroup = curve.prime_subgroup();
+        assert_eq!(subgroup.order, 24);
+        assert_eq!(subgroup.subgroup_order, 3);
+        assert_eq!(subgroup.cofactor, 8);
+
+        let mut outside = 0;
+        for point in curve.points() {
+            let cleared = subgroup.clear_cofactor(&point).unwrap();
+            assert!(subgroup.contains(&cleared).unwrap());
+
+            let in_subgroup = subgroup.contains(&point).unwrap();
+            assert_eq!(in_subgroup, curve.multiply(&point, 3).is_infinity);
+            if !in_subgroup {
+                outside += 1;
+            }
+        }
+        assert!(outside > 0);
+
+        // prime order curve: every point is in the subgroup
+        let curve = EllipticCurve::new(Field::new(2, 17), Field::new(2, 17));
+        let subgroup = curve.prime_subgroup();
+        assert!(curve.points().all(|p| subgroup.contains(&p).unwrap()));
+    }
+
+    #[test]
+    fn test_validate_scalar() {
+        let subgroup = EllipticCurve::new(Field::new(2, 17), Field::new(2, 17)).prime_subgroup();
+        assert_eq!(subgroup.validate_scalar(5).unwrap(), 5);
+        assert_eq!(subgroup.validate_scalar(18).unwrap(), 18);
+        assert!(matches!(
+            subgroup.validate_scalar(0),
+            Err(EllipticCurveError::InvalidScalar(0))
+        ));
+        assert!(matches!(
+            subgroup.validate_scalar(19),
+            Err(EllipticCurveError::InvalidScalar(19))
+        ));
+    }
+}
@@ -0,0 +1,276 @@
+use crate::short_weierstras::{ECPoint, EllipticCurve, EllipticCurveError, EllipticCurveTrait};
+use field::extension_field::ExtensionField;
+use field::field::{Field, FieldTrait};
+use field::params::is_prime;
+
+/// F_p^2 = F_p[i] / (i^2 + 1), a field whenever p = 3 mod 4
+pub type Fp2 = ExtensionField<Field, 2>;
+
+// The reduced Tate pairing on the supersingular curve
+//
+// y^2 = x^3 + x over F_p, p = 3 mod 4
+//
+// which has p + 1 points and embedding degree 2: for any prime r | p + 1 the r-th
+// roots of unity live in F_p^2. The distortion map ψ(x, y) = (-x, iy) sends E(F_p)[r]
+// to a different subgroup of E[r], which makes the pairing symmetric and non degenerate:
+//
+// e(P, Q) = f_(r, P)(ψ(Q))^((p^2 - 1) / r)
+//
+// where f_(r, P) is the function with divisor r(P) - r(O), built by Miller's loop.
+#[derive(Debug, Clone, Copy)]
+pub struct TatePairing {
+    pub curve: EllipticCurve,
+    pub order: usize,
+}
+
+impl TatePairing {
+    /// The pairing on E[r] for y^2 = x^3 + x mod p
+    pub fn new(modulus: usize, order: usize) -> Self {
+        assert_eq!(modulus % 4, 3, "Modulus should be 3 mod 4");
+        assert!(is_prime(modulus as u64), "Modulus should be prime");
+        assert!(is_prime(order as u64), "Order should be prime");
+        assert!(
+            (modulus + 1).is_multiple_of(order),
+            "Order should divide p + 1"
+        );
+
+        let curve = EllipticCurve::new(Field::new(1, modulus), Field::new(0, modulus));
+        Self { curve, order }
+    }
+
+    /// e(P, Q), for P and Q in E(F_p)[r]
+    pub fn pairing(&self, point_p: &ECPoint, point_q: &ECPoint) -> Result<Fp2, EllipticCurveError> {
+        self.check_torsion(point_p)?;
+        self.check_torsion(point_q)?;
+
+        if point_p.is_infinity || point_q.is_infinity {
+            return Ok(self.fp2_one());
+        }
+
+        let f = self.miller_loop(point_p, &self.distortion(point_q));
+        Ok(self.final_exponentiation(&f))
+    }
+
+    /// ψ(x, y) = (-x, iy)
+    pub fn distortion(&self, point: &ECPoint) -> (Fp2, Fp2) {
+        let zero = point.x.zero();
+        let x = Fp2::new([zero - point.x, zero], self.fp2_modulus());
+        let y = Fp2::new([zero, point.y], self.fp2_modulus());
+        (x, y)
+    }
+
+    /// f_(r, P)(Q), double-and-add over the bits of r. The vertical lines in the
+    /// denominators take values in F_p, which the final exponentiation sends to 1,
+    /// so they are left out.
+    pub fn miller_loop(&self, point: &ECPoint, q: &(Fp2, Fp2)) -> Fp2 {
+        let mut f = self.fp2_one();
+        let mut t = *point;
+
+        let bits = usize::BITS - self.order.leading_zeros();
+        for i in (0..bits - 1).rev() {
+            f = f * f * self.line(&t, &t, q);
+            t = self.add_points(&t, &t);
+
+            if (self.order >> i) & 1 == 1 {
+                f = f * self.line(&t, point, q);
+                t = self.add_points(&t, point);
+            }
+        }
+
+        f
+    }
+
+    /// f^((p^2 - 1) / r) = (f^p / f)^((p + 1) / r), f^p being the frobenius
+    pub fn final_exponentiation(&self, f: &Fp2) -> Fp2 {
+        let p = self.curve.a.modulus();
+        let f_p_minus_one = f.frobenius() / *f;
+        f_p_minus_one.pow_limbs(&[((p + 1) / self.order) as u64])
+    }
+
+    // the line through T and S (the tangent when T = S) evaluated at Q:
+    // y_Q - y_T - λ(x_Q - x_T), or x_Q - x_T when the line is vertical
+    fn line(&self, t: &ECPoint, s: &ECPoint, q: &(Fp2, Fp2)) -> Fp2 {
+        let (x_q, y_q) = *q;
+        let x_t = self.embed(t.x);
+        let y_t = self.embed(t.y);
+
+        let slope = if t.x != s.x {
+            (s.y - t.y) / (s.x - t.x)
+        } else if t.y == s.y && t.y.value != 0 {
+            let three = Field::new(3, t.x.modulus());
+            (three * t.x * t.x + self.curve.a) / (t.y + t.y)
+        } else {
+            return x_q - x_t;
+        };
+
+        y_q - y_t - self.embed(slope) * (x_q - x_t)
+    }
+
+    // the affine formulas can't add a point to its inverse
+    fn add_points(&self, a: &ECPoint, b: &ECPoint) -> ECPoint {
+        if !a.is_infinity && !b.is_infinity && a.x == b.x && a.y != b.y {
+            return EllipticCurve::zero(&self.curve);
+        }
+        self.curve
+            .add(a, b)
+            .expect("Miller loop points should be on the curve")
+    }
+
+    // on the curve and r * P = O
+    fn check_torsion(&self, point: &ECPoint) -> Result<(), EllipticCurveError> {
+        if !self.curve.is_on_curve(point) || point.curve != self.curve {
+            return Err(EllipticCurveError::InvalidPoint(*point));
+        }
+
+        let r_point = self.curve.montgomery_ladder(point, &[self.order as u64])?;
+        if !r_point.is_infinity {
+            return Err(EllipticCurveError::InvalidPoint(*point));
+        }
+
+        Ok(())
+    }
+
+    fn fp2_modulus(&self) -> [Field; 2] {
+        // i^2 + 1
+        [self.curve.a.one(), self.curve.a.zero()]
+    }
+
+    fn fp2_one(&self) -> Fp2 {
+        self.embed(self.curve.a.one())
+    }
+
+    fn embed(&self, value: Field) -> Fp2 {
+        Fp2::from_base(value, self.fp2_modulus())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a generator of E(F_p)[r], by clearing the cofactor (p + 1) / r
+    fn generator(pairing: &TatePairing) -> ECPoint {
+        let p = pairing.curve.a.modulus();
+        let cofactor = ((p + 1) / pairing.order) as u64;
+
+        (0..p)
+            .filter_map(|x| {
+                let x = Field::new(x, p);
+                let y = (x * x * x + x).sqrt()?;
+                let point = pairing.curve.ec_point(x, y);
+                let cleared = pairing
+                    .curve
+                    .montgomery_ladder(&point, &[cofactor])
+                    .unwrap();
+                (!cleared.is_infinity).then_some(cleared)
+            })
+            .next()
+            .unwrap()
+    }
+
+    fn multiply(pairing: &TatePairing, point: &ECPoint, scalar: usize) -> ECPoint {
+        pairing
+            .curve
+            .montgomery_ladder(point, &[scalar as u64])
+            .unwrap()
+    }
+
+    #[test]
+    fn test_bilinearity() {
+        for (p, r) in [(43, 11), (10007, 139)] {
+            let pairing = TatePairing::new(p, r);
+            let g = generator(&pairing);
+            let e_gg = pairing.pairing(&g, &g).unwrap();
+
+            // non degenerate, and an r-th root of unity
+            assert_ne!(e_gg, pairing.fp2_one());
+            assert_eq!(e_gg.pow_limbs(&[r as u64]), pairing.fp2_one());
+
+            for a in [1, 2, 5, r - 1] {
+                for b in [1, 3, 7, r - 2] {
+                    let a_g = multiply(&pairing, &g, a);
+                    let b_g = multiply(&pairing, &g, b);
+
+                    // e(aG, bG) = e(G, G)^ab = e(bG, aG)
+                    let expected = e_gg.pow_limbs(&[(a * b) as u64]);
+                    assert_eq!(pairing.pairing(&a_g, &b_g).unwrap(), expected);
+                    assert_eq!(pairing.pairing(&b_g, &a_g).unwrap(), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_linearity_in_each_argument() {
+        let pairing = TatePairing::new(10007, 139);
+        let g = generator(&pairing);
+        let p1 = multiply(&pairing, &g, 17);
+        let p2 = multiply(&pairing, &g, 101);
+        let q = multiply(&pairing, &g, 42);
+
+        // e(P1 + P2, Q) = e(P1, Q) e(P2, Q)
+        let sum = pairing.curve.add(&p1, &p2).unwrap();
+        assert_eq!(
+            pairing.pairing(&sum, &q).unwrap(),
+            pairing.pairing(&p1, &q).unwrap() * pairing.pairing(&p2, &q).unwrap()
+        );
+
+        // e(-P, Q) = e(P, Q)^(-1)
+        let neg = pairing.curve.ec_point(p1.x, p1.y.zero() - p1.y);
+        let product = pairing.pairing(&neg, &q).unwrap() * pairing.pairing(&p1, &q).unwrap();
+        assert_eq!(product, pairing.fp2_one());
+    }
+
+    #[test]
+    fn test_kzg_style_opening() {
+        // commit to f(x) = 3 + 2x + x^2 at a secret τ, open at z with q(x) = (f(x) - f(z)) / (x - z)
+        // and check e(C - f(z) G, G) = e(π, (τ - z) G)
+        let pairing = TatePairing::new(10007, 139);
+        let r = pairing.order;
+        let g = generator(&pairing);
+
+        let (tau, z) = (77, 5);
+        let f = |x: usize| (3 + 2 * x + x * x) % r;
+        // f(x) - f(z) = (x - z)(x + z + 2)
+        let q = |x: usize| (x + z + 2) % r;
+
+        let commitment = multiply(&pairing, &g, f(tau));
+        let proof = multiply(&pairing, &g, q(tau));
+
+        let lhs_point = multiply(&pairing, &g, (f(tau) + r - f(z)) % r);
+        let rhs_point = multiply(&pairing, &g, (tau + r - z) % r);
+        assert_eq!(
+            pairing
+                .curve
+                .add(&commitment, &multiply(&pairing, &g, r - f(z)))
+                .unwrap(),
+            lhs_point
+        );
+
+        let lhs = pairing.pairing(&lhs_point, &g).unwrap();
+        let rhs = pairing.pairing(&proof, &rhs_point).unwrap();
+        assert_eq!(lhs, rhs);
+
+        // a wrong evaluation does not verify
+        let wrong = multiply(&pairing, &g, (f(tau) + r - f(z) - 1) % r);
+        assert_ne!(pairing.pairing(&wrong, &g).unwrap(), rhs);
+    }
+
+    #[test]
+    fn test_invalid_points() {
+        let pairing = TatePairing::new(43, 11);
+        let g = generator(&pairing);
+        let infinity = EllipticCurve::zero(&pairing.curve);
+
+        assert_eq!(pairing.pairing(&g, &infinity).unwrap(), pairing.fp2_one());
+        assert_eq!(pairing.pairing(&infinity, &g).unwrap(), pairing.fp2_one());
+
+        // not on the curve
+        let off_curve = pairing.curve.ec_point(Field::new(1, 43), Field::new(1, 43));
+        assert!(pairing.pairing(&g, &off_curve).is_err());
+
+        // on the curve, but (0, 0) has order 2, not 11
+        let order_two = pairing.curve.ec_point(Field::new(0, 43), Field::new(0, 43));
+        assert!(pairing.pairing(&order_two, &g).is_err());
+    }
+}
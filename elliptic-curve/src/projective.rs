@@ -0,0 +1,199 @@
+use crate::short_weierstras::{CurveField, ECPoint, EllipticCurve};
+use crate::utils::double;
+use field::field::Field;
+
+// Homogeneous projective coordinates: (X, Y, Z) represents the affine point
+// (X / Z, Y / Z) and the curve equation becomes Y^2 Z = X^3 + aXZ^2 + bZ^3.
+// The point at infinity is any point with Z = 0, canonically (0, 1, 0).
+#[derive(Debug, Clone, Copy)]
+pub struct ProjectivePoint<F: CurveField = Field> {
+    pub x: F,
+    pub y: F,
+    pub z: F,
+    pub curve: EllipticCurve<F>,
+}
+
+impl<F: CurveField> ProjectivePoint<F> {
+    pub fn new(x: F, y: F, z: F, curve: EllipticCurve<F>) -> Self {
+        Self { x, y, z, curve }
+    }
+
+    pub fn infinity(curve: &EllipticCurve<F>) -> Self {
+        Self::new(curve.a.zero(), curve.a.one(), curve.a.zero(), *curve)
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        self.z == self.z.zero()
+    }
+
+    pub fn from_affine(point: &ECPoint<F>) -> Self {
+        if point.is_infinity {
+            return Self::infinity(&point.curve);
+        }
+        Self::new(point.x, point.y, point.x.one(), point.curve)
+    }
+
+    /// (X, Y, Z) -> (X / Z, Y / Z), costs one field inversion
+    pub fn to_affine(&self) -> ECPoint<F> {
+        if self.is_infinity() {
+            return EllipticCurve::zero(&self.curve);
+        }
+
+        let z_inv = self.z.inverse().expect("Z should be invertible");
+        ECPoint::new(self.x * z_inv, self.y * z_inv, self.curve)
+    }
+
+    // https://hyperelliptic.org/EFD/g1p/auto-shortw-projective.html#doubling-dbl-2007-bl
+    pub fn double(&self) -> Self {
+        if self.is_infinity() {
+            return *self;
+        }
+
+        let xx = self.x * self.x;
+        let zz = self.z * self.z;
+
+        // w = a * ZZ + 3 * XX
+        let w = self.curve.a * zz + xx + xx + xx;
+
+        // s = 2 * Y * Z, R = Y * s
+        let s = double(self.y * self.z);
+        let ss = s * s;
+        let sss = s * ss;
+        let r = self.y * s;
+        let rr = r * r;
+
+        // B = (X + R)^2 - XX - RR, h = w^2 - 2 * B
+        let x_plus_r = self.x + r;
+        let b = x_plus_r * x_plus_r - xx - rr;
+        let h = w * w - double(b);
+
+        // X3 = h * s, Y3 = w * (B - h) - 2 * RR, Z3 = sss
+        let x3 = h * s;
+        let y3 = w * (b - h) - double(rr);
+
+        Self::new(x3, y3, sss, self.curve)
+    }
+
+    // https://hyperelliptic.org/EFD/g1p/auto-shortw-projective.html#addition-add-1998-cmo-2
+    pub fn add(&self, other: &ProjectivePoint<F>) -> Self {
+        if self.is_infinity() {
+            return *other;
+        }
+        if other.is_infinity() {
+            return *self;
+        }
+
+        let y1z2 = self.y * other.z;
+        let x1z2 = self.x * other.z;
+        let z1z2 = self.z * other.z;
+
+        // u = Y2 * Z1 - Y1 * Z2, v = X2 * Z1 - X1 * Z2
+        let u = other.y * self.z - y1z2;
+        let v = other.x * self.z - x1z2;
+
+        // same x coordinate: either the same point or inverses of each other
+        if v == v.zero() {
+            if u == u.zero() {
+                return self.double();
+            }
+            return Self::infinity(&self.curve);
+        }
+
+        let uu = u * u;
+        let vv = v * v;
+        let vvv = v * vv;
+        let r = vv * x1z2;
+
+        // A = uu * Z1Z2 - vvv - 2 * R
+        let a = uu * z1z2 - vvv - double(r);
+
+        // X3 = v * A, Y3 = u * (R - A) - vvv * Y1Z2, Z3 = vvv * Z1Z2
+        let x3 = v * a;
+        let y3 = u * (r - a) - vvv * y1z2;
+        let z3 = vvv * z1z2;
+
+        Self::new(x3, y3, z3, self.curve)
+    }
+
+    pub fn neg(&self) -> Self {
+        Self::new(self.x, self.y.zero() - self.y, self.z, self.curve)
+    }
+}
+
+impl<F: CurveField> From<ECPoint<F>> for ProjectivePoint<F> {
+    fn from(point: ECPoint<F>) -> Self {
+        Self::from_affine(&point)
+    }
+}
+
+impl<F: CurveField> From<ProjectivePoint<F>> for ECPoint<F> {
+    fn from(point: ProjectivePoint<F>) -> Self {
+        point.to_affine()
+    }
+}
+
+// (X1, Y1, Z1) == (X2, Y2, Z2) iff X1 Z2 == X2 Z1 and Y1 Z2 == Y2 Z1
+impl<F: CurveField> PartialEq for ProjectivePoint<F> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.is_infinity() || other.is_infinity() {
+            return self.is_infinity() == other.is_infinity();
+        }
+
+        self.x * other.z == other.x * self.z && self.y * other.z == other.y * self.z
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short_weierstras::EllipticCurveTrait;
+
+    fn setup_curve() -> EllipticCurve {
+        // y^2 = x^3 + 2x + 2 mod 17
+        EllipticCurve::new(Field::new(2, 17), Field::new(2, 17))
+    }
+
+    #[test]
+    fn test_affine_round_trip() {
+        let curve = setup_curve();
+        let point = curve.ec_point(Field::new(6, 17), Field::new(3, 17));
+
+        let projective = ProjectivePoint::from_affine(&point);
+        assert_eq!(projective.to_affine(), point);
+
+        // same point, different representative: (X t, Y t, t)
+        let t = Field::new(11, 17);
+        let scaled = ProjectivePoint::new(point.x * t, point.y * t, t, curve);
+        assert_eq!(scaled, projective);
+        assert_eq!(ECPoint::from(scaled), point);
+
+        let infinity = ProjectivePoint::from(EllipticCurve::zero(&curve));
+        assert!(infinity.is_infinity());
+        assert_eq!(infinity.to_affine(), EllipticCurve::zero(&curve));
+    }
+
+    #[test]
+    fn test_add_and_double_match_affine() {
+        let curve = setup_curve();
+        let generator = curve.ec_point(Field::new(5, 17), Field::new(1, 17));
+
+        // walk the whole cyclic group of order 19 both ways
+        let mut affine = generator;
+        let mut projective = ProjectivePoint::from(generator);
+        for _ in 1..18 {
+            assert_eq!(projective.to_affine(), affine);
+            assert_eq!(
+                projective.double().to_affine(),
+                curve.double(&affine).unwrap()
+            );
+
+            affine = curve.add(&affine, &generator).unwrap();
+            projective = projective.add(&ProjectivePoint::from(generator));
+        }
+
+        // 18 * G + G = 19 * G = O
+        let sum = projective.add(&ProjectivePoint::from(generator));
+        assert!(sum.is_infinity());
+        assert!(projective.add(&projective.neg()).is_infinity());
+    }
+}
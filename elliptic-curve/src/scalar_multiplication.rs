@@ -0,0 +1,263 @@
+use crate::jacobian::JacobianPoint;
+use crate::short_weierstras::{
+    CurveField, ECPoint, EllipticCurve, EllipticCurveError, EllipticCurveTrait,
+};
+use crate::utils::{limb_bit, wnaf};
+
+// Scalars are little-endian u64 limbs, so any width works: [k0, k1] = k0 + k1 * 2^64.
+impl<F: CurveField> EllipticCurve<F> {
+    /// Montgomery ladder: k * P in exactly 64 * scalar.len() steps of one addition
+    /// and one doubling each, whatever the value of the scalar.
+    ///
+    /// Invariant: R1 - R0 = P. For every bit b, from the most significant one,
+    /// b == 0 => (R0, R1) = (2 R0, R0 + R1)
+    /// b == 1 => (R0, R1) = (R0 + R1, 2 R1)
+    /// Both cases are the same computation once R0 and R1 are conditionally swapped.
+    ///
+    /// The ladder starts from (O, P) and every step is branch free: the swaps and the
+    /// infinity and equal point cases of the addition are arithmetic selections. The
+    /// timing of the field arithmetic itself is up to `F`.
+    pub fn montgomery_ladder(
+        &self,
+        point: &ECPoint<F>,
+        scalar: &[u64],
+    ) -> Result<ECPoint<F>, EllipticCurveError<F>> {
+        if !self.is_on_curve(point) {
+            return Err(EllipticCurveError::InvalidPoint(*point));
+        }
+
+        let mut r0 = JacobianPoint::infinity(self);
+        let mut r1 = JacobianPoint::from_affine(point);
+
+        for i in (0..scalar.len() * 64).rev() {
+            let b = limb_bit(scalar, i);
+
+            JacobianPoint::conditional_swap(&mut r0, &mut r1, b);
+            r1 = r0.add_branch_free(&r1);
+            r0 = r0.double();
+            JacobianPoint::conditional_swap(&mut r0, &mut r1, b);
+        }
+
+        let new_point = r0.to_affine();
+        assert!(self.is_on_curve(&new_point));
+
+        Ok(new_point)
+    }
+
+    /// k * P with the width-w NAF of k and a table of P, 3P, ..., (2^(w-1) - 1) P.
+    ///
+    /// Needs about bits / (w + 1) additions instead of bits / 2, but the sequence of
+    /// operations reveals the scalar, so only use it for public scalars.
+    /// The table has 2^(w-2) points, so w is limited to 2..=8.
+    pub fn wnaf_multiplication(
+        &self,
+        point: &ECPoint<F>,
+        scalar: &[u64],
+        window: usize,
+    ) -> Result<ECPoint<F>, EllipticCurveError<F>> {
+        if !(2..=8).contains(&window) {
+            return Err(EllipticCurveError::InvalidWindow(window));
+        }
+
+        if !self.is_on_curve(point) {
+            return Err(EllipticCurveError::InvalidPoint(*point));
+        }
+
+        let digits = wnaf(scalar, window);
+
+        // table[i] = (2i + 1) P
+        let base = JacobianPoint::from_affine(point);
+        let double_base = base.double();
+        let mut table = vec![base];
+        for i in 1..1 << (window - 2) {
+            table.push(table[i - 1].add(&double_base));
+        }
+
+        let mut result = JacobianPoint::infinity(self);
+        for digit in digits.iter().rev() {
+            result = result.double();
+
+            let index = (digit.unsigned_abs() / 2) as usize;
+            if *digit > 0 {
+                result = result.add(&table[index]);
+            } else if *digit < 0 {
+                result = result.add(&table[index].neg());
+            }
+        }
+
+        let new_point = result.to_affine();
+        assert!(self.is_on_curve(&new_point));
+
+        Ok(new_point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use field::big_field::{limbs_from_hex, Field256, MontgomeryParams};
+    use field::field::{Field, FieldTrait};
+    use field::small_prime_field::SmallPrimeField;
+
+    fn setup_curve() -> EllipticCurve {
+        // y^2 = x^3 + 2x + 2 mod 17, a cyclic group of order 19
+        EllipticCurve::new(Field::new(2, 17), Field::new(2, 17))
+    }
+
+    #[test]
+    fn test_matches_double_and_add() {
+        let curve = setup_curve();
+        let point = curve.ec_point(Field::new(5, 17), Field::new(1, 17));
+
+        for k in 1..60 {
+            let expected = curve.scalar_multiplication(&point, k).unwrap();
+            let scalar = [k as u64];
+
+            assert_eq!(curve.montgomery_ladder(&point, &scalar).unwrap(), expected);
+            for window in 2..6 {
+                let result = curve.wnaf_multiplication(&point, &scalar, window);
+                assert_eq!(result.unwrap(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_zero_and_infinity() {
+        let curve = setup_curve();
+        let point = curve.ec_point(Field::new(5, 17), Field::new(1, 17));
+        let infinity = EllipticCurve::zero(&curve);
+
+        assert_eq!(curve.montgomery_ladder(&point, &[0]).unwrap(), infinity);
+        assert_eq!(curve.montgomery_ladder(&point, &[]).unwrap(), infinity);
+        assert_eq!(
+            curve.wnaf_multiplication(&point, &[0], 4).unwrap(),
+            infinity
+        );
+
+        // the order of the group
+        assert_eq!(curve.montgomery_ladder(&point, &[19]).unwrap(), infinity);
+        assert_eq!(curve.montgomery_ladder(&infinity, &[7]).unwrap(), infinity);
+        assert_eq!(
+            curve.wnaf_multiplication(&infinity, &[7], 3).unwrap(),
+            infinity
+        );
+
+        let invalid = curve.ec_point(Field::new(4, 17), Field::new(1, 17));
+        assert!(curve.montgomery_ladder(&invalid, &[3]).is_err());
+        assert!(curve.wnaf_multiplication(&invalid, &[3], 3).is_err());
+    }
+
+    #[test]
+    fn test_wnaf_window_too_large() {
+        let curve = setup_curve();
+        let point = curve.ec_point(Field::new(5, 17), Field::new(1, 17));
+
+        assert!(curve.wnaf_multiplication(&point, &[7], 9).is_err());
+    }
+
+    #[test]
+    fn test_multi_limb_scalar() {
+        let curve = setup_curve();
+        let point = curve.ec_point(Field::new(5, 17), Field::new(1, 17));
+
+        // k = k0 + k1 * 2^64 + k2 * 2^128, reduced mod 19 for the reference
+        let scalar = [0xdead_beef_0123_4567, 0x0f0f_0f0f_0f0f_0f0f, 0x42];
+        let reduced = scalar
+            .iter()
+            .rev()
+            .fold(0u128, |acc, limb| ((acc << 64) + *limb as u128) % 19);
+
+        let expected = curve
+            .scalar_multiplication(&point, reduced as usize)
+            .unwrap();
+        assert_eq!(curve.montgomery_ladder(&point, &scalar).unwrap(), expected);
+        assert_eq!(
+            curve.wnaf_multiplication(&point, &scalar, 5).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_secp256k1() {
+        // y^2 = x^3 + 7 over the 256-bit secp256k1 base field
+        let params = MontgomeryParams::from_hex(
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+        );
+        let element = |hex: &str| Field256::from_hex(hex, params);
+        let curve =
+            EllipticCurve::new(Field256::from_u64(0, params), Field256::from_u64(7, params));
+
+        let generator = curve.ec_point(
+            element("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"),
+            element("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"),
+        );
+        let double = curve.ec_point(
+            element("c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5"),
+            element("1ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a"),
+        );
+        assert!(curve.is_on_curve(&generator));
+        assert_eq!(curve.double(&generator).unwrap(), double);
+        assert_eq!(curve.montgomery_ladder(&generator, &[2]).unwrap(), double);
+        assert_eq!(curve.scalar_multiplication(&generator, 2).unwrap(), double);
+
+        // n G = O and (n - 1) G = -G for the group order n
+        let order: [u64; 4] =
+            limbs_from_hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+        assert!(
+            curve
+                .montgomery_ladder(&generator, &order)
+                .unwrap()
+                .is_infinity
+        );
+
+        let mut order_minus_one = order;
+        order_minus_one[0] -= 1;
+        let minus_generator = curve.ec_point(generator.x, generator.y.zero() - generator.y);
+        assert_eq!(
+            curve
+                .montgomery_ladder(&generator, &order_minus_one)
+                .unwrap(),
+            minus_generator
+        );
+        assert_eq!(
+            curve
+                .wnaf_multiplication(&generator, &order_minus_one, 5)
+                .unwrap(),
+            minus_generator
+        );
+    }
+
+    #[test]
+    fn test_small_prime_field() {
+        type F17 = SmallPrimeField<17>;
+
+        // the same curve over SmallPrimeField<17> gives the same multiples
+        let curve = setup_curve();
+        let small_curve = EllipticCurve::new(F17::from(2u64), F17::from(2u64));
+        let to_small = |point: ECPoint| {
+            if point.is_infinity {
+                return EllipticCurve::zero(&small_curve);
+            }
+            small_curve.ec_point(
+                F17::from(point.x.value as u64),
+                F17::from(point.y.value as u64),
+            )
+        };
+
+        let point = curve.ec_point(Field::new(5, 17), Field::new(1, 17));
+        let small_point = to_small(point);
+        for k in 0..40u64 {
+            let expected = to_small(curve.montgomery_ladder(&point, &[k]).unwrap());
+            assert_eq!(
+                small_curve.montgomery_ladder(&small_point, &[k]).unwrap(),
+                expected
+            );
+            assert_eq!(
+                small_curve
+                    .wnaf_multiplication(&small_point, &[k], 3)
+                    .unwrap(),
+                expected
+            );
+        }
+    }
+}
@@ -1,45 +1,83 @@
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Sub};
+
 use field::field::{Field, FieldTrait};
 
+use crate::jacobian::JacobianPoint;
 use crate::utils::{bit, bits};
 
+/// The field arithmetic the curve formulas need, implemented by `Field` as well as
+/// `BigField` and `SmallPrimeField` for real curves
+pub trait CurveField:
+    FieldTrait
+    + Copy
+    + PartialEq
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+}
+
+impl<F> CurveField for F where
+    F: FieldTrait
+        + Copy
+        + PartialEq
+        + Debug
+        + Add<Output = F>
+        + Sub<Output = F>
+        + Mul<Output = F>
+        + Div<Output = F>
+{
+}
+
 // An elliptic curve is denoted by the equation
 // y^2 = x^3 + ax + b -> Short weierstras
 // y^2 + a1xy + a3y = x^3 + a2x^2 + a4x + a6 -> General weierstras
 // If 4a^3 + 27b^2 != 0 the curve is non singular
+//
+// The coordinates live in any `CurveField`, `Field` by default.
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
-pub struct EllipticCurve {
-    pub a: Field,
-    pub b: Field,
+pub struct EllipticCurve<F: CurveField = Field> {
+    pub a: F,
+    pub b: F,
 }
 
 #[derive(Debug)]
-pub enum EllipticCurveError {
-    InvalidPoint(ECPoint),
+pub enum EllipticCurveError<F: CurveField = Field> {
+    InvalidPoint(ECPoint<F>),
     InvalidScalar(usize),
+    InvalidEncoding(Vec<u8>),
+    InvalidWindow(usize),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
-pub struct ECPoint {
-    pub x: Field,
-    pub y: Field,
-    pub curve: EllipticCurve,
+pub struct ECPoint<F: CurveField = Field> {
+    pub x: F,
+    pub y: F,
+    pub curve: EllipticCurve<F>,
     pub is_infinity: bool,
 }
 
-pub trait EllipticCurveTrait {
-    fn ec_point(&self, x: Field, y: Field) -> ECPoint;
-    fn is_on_curve(&self, ec_point: &ECPoint) -> bool;
-    fn add(&self, point_a: &ECPoint, point_b: &ECPoint) -> Result<ECPoint, EllipticCurveError>;
-    fn double(&self, point_a: &ECPoint) -> Result<ECPoint, EllipticCurveError>;
+pub trait EllipticCurveTrait<F: CurveField = Field> {
+    fn ec_point(&self, x: F, y: F) -> ECPoint<F>;
+    fn is_on_curve(&self, ec_point: &ECPoint<F>) -> bool;
+    fn add(
+        &self,
+        point_a: &ECPoint<F>,
+        point_b: &ECPoint<F>,
+    ) -> Result<ECPoint<F>, EllipticCurveError<F>>;
+    fn double(&self, point_a: &ECPoint<F>) -> Result<ECPoint<F>, EllipticCurveError<F>>;
     fn scalar_multiplication(
         &self,
-        point: &ECPoint,
+        point: &ECPoint<F>,
         scalar: usize,
-    ) -> Result<ECPoint, EllipticCurveError>;
+    ) -> Result<ECPoint<F>, EllipticCurveError<F>>;
 }
 
-impl ECPoint {
-    pub fn new(x: Field, y: Field, curve: EllipticCurve) -> Self {
+impl<F: CurveField> ECPoint<F> {
+    pub fn new(x: F, y: F, curve: EllipticCurve<F>) -> Self {
         Self {
             x,
             y,
@@ -49,14 +87,14 @@ impl ECPoint {
     }
 }
 
-impl EllipticCurve {
-    pub fn new(a: Field, b: Field) -> EllipticCurve {
+impl<F: CurveField> EllipticCurve<F> {
+    pub fn new(a: F, b: F) -> EllipticCurve<F> {
         EllipticCurve { a, b }
     }
-    pub fn zero(curve: &EllipticCurve) -> ECPoint {
+    pub fn zero(curve: &EllipticCurve<F>) -> ECPoint<F> {
         ECPoint {
-            x: Field::new(0, curve.a.modulus()),
-            y: Field::new(0, curve.a.modulus()),
+            x: curve.a.zero(),
+            y: curve.a.zero(),
             curve: *curve,
             is_infinity: true,
         }
@@ -65,13 +103,7 @@ impl EllipticCurve {
     /// x3 = λ^2 - x1 - x2 mod p
     /// y3 = λ(x1 - x3) - y1 mod p
     ///
-    fn compute_x3_y3_add(
-        &self,
-        slope: &Field,
-        x1: &Field,
-        y1: &Field,
-        x2: &Field,
-    ) -> (Field, Field) {
+    fn compute_x3_y3_add(&self, slope: &F, x1: &F, y1: &F, x2: &F) -> (F, F) {
         // x3
         let λ2 = slope.pow(2);
         let λ2_minus_x1 = λ2 - *x1;
@@ -88,13 +120,7 @@ impl EllipticCurve {
     /// x3 = λ^2 - 2x1 mod p
     /// y3 = λ(x1 - x3) - y1 mod p
     ///
-    fn compute_x3_y3_double(
-        &self,
-        slope: &Field,
-        x1: &Field,
-        y1: &Field,
-        two: &Field,
-    ) -> (Field, Field) {
+    fn compute_x3_y3_double(&self, slope: &F, x1: &F, y1: &F, two: &F) -> (F, F) {
         // x3
         let λ2 = slope.pow(2);
         let two_x1 = *two * *x1;
@@ -109,8 +135,8 @@ impl EllipticCurve {
     }
 }
 
-impl EllipticCurveTrait for EllipticCurve {
-    fn ec_point(&self, x: Field, y: Field) -> ECPoint {
+impl<F: CurveField> EllipticCurveTrait<F> for EllipticCurve<F> {
+    fn ec_point(&self, x: F, y: F) -> ECPoint<F> {
         ECPoint::new(x, y, *self)
     }
 
@@ -118,7 +144,7 @@ impl EllipticCurveTrait for EllipticCurve {
     ///
     /// if `y^2 = x^3 + ax + b mod p` then returns `true`, if not, returns `false`.
     ///
-    fn is_on_curve(&self, ec_point: &ECPoint) -> bool {
+    fn is_on_curve(&self, ec_point: &ECPoint<F>) -> bool {
         if ec_point.is_infinity {
             return true;
         }
@@ -141,7 +167,11 @@ impl EllipticCurveTrait for EllipticCurve {
     // λ = --------- mod p
     //     (x2 - x1)
     //
-    fn add(&self, point_a: &ECPoint, point_b: &ECPoint) -> Result<ECPoint, EllipticCurveError> {
+    fn add(
+        &self,
+        point_a: &ECPoint<F>,
+        point_b: &ECPoint<F>,
+    ) -> Result<ECPoint<F>, EllipticCurveError<F>> {
         if !self.is_on_curve(&point_a) {
             return Err(EllipticCurveError::InvalidPoint(*point_a));
         }
@@ -177,7 +207,7 @@ impl EllipticCurveTrait for EllipticCurve {
     //     (3 * x1^2 + a)
     // λ = -------------- mod p
     //        (2 * y1)
-    fn double(&self, point_a: &ECPoint) -> Result<ECPoint, EllipticCurveError> {
+    fn double(&self, point_a: &ECPoint<F>) -> Result<ECPoint<F>, EllipticCurveError<F>> {
         if !self.is_on_curve(&point_a) {
             return Err(EllipticCurveError::InvalidPoint(*point_a));
         }
@@ -187,8 +217,8 @@ impl EllipticCurveTrait for EllipticCurve {
         }
 
         let x1_2 = point_a.x.pow(2);
-        let two_as_a_field = Field::new(2, x1_2.modulus());
-        let three_as_a_field = Field::new(3, x1_2.modulus());
+        let two_as_a_field = x1_2.one() + x1_2.one();
+        let three_as_a_field = two_as_a_field + x1_2.one();
 
         // numerator
         let three_multiplied_by_x1_2 = three_as_a_field * x1_2;
@@ -209,9 +239,9 @@ impl EllipticCurveTrait for EllipticCurve {
 
     fn scalar_multiplication(
         &self,
-        point: &ECPoint,
+        point: &ECPoint<F>,
         scalar: usize,
-    ) -> Result<ECPoint, EllipticCurveError> {
+    ) -> Result<ECPoint<F>, EllipticCurveError<F>> {
         if !self.is_on_curve(point) {
             return Err(EllipticCurveError::InvalidPoint(*point));
         }
@@ -224,15 +254,18 @@ impl EllipticCurveTrait for EllipticCurve {
             return Err(EllipticCurveError::InvalidScalar(scalar));
         }
 
-        let mut new_point = *point;
+        // double-and-add in Jacobian coordinates, a single inversion at the end
+        let base = JacobianPoint::from_affine(point);
+        let mut result = base;
 
         for i in (0..bits(scalar) - 1).rev() {
-            new_point = self.double(&new_point)?;
+            result = result.double();
             if bit(scalar, i) {
-                new_point = self.add(&new_point, point)?;
+                result = result.add(&base);
             }
         }
 
+        let new_point = result.to_affine();
         assert!(self.is_on_curve(&new_point));
 
         Ok(new_point)
@@ -0,0 +1,289 @@
+use crate::montgomery::{MontgomeryCurve, MontgomeryPoint};
+use crate::short_weierstras::{ECPoint, EllipticCurve};
+use crate::utils::limb_bit;
+use field::field::{Field, FieldTrait};
+
+// A twisted Edwards curve is denoted by the equation
+// ax^2 + y^2 = 1 + dx^2y^2
+// with a, d != 0 and a != d. The neutral element is (0, 1) and -(x, y) = (-x, y).
+// Ed25519 is a = -1, d = -121665 / 121666 over 2^255 - 19.
+//
+// Only complete curves, a a square and d not, are accepted: otherwise the addition
+// law divides by zero for some pairs of points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TwistedEdwardsCurve {
+    pub a: Field,
+    pub d: Field,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdwardsPoint {
+    pub x: Field,
+    pub y: Field,
+    pub curve: TwistedEdwardsCurve,
+}
+
+impl TwistedEdwardsCurve {
+    pub fn new(a: Field, d: Field) -> Self {
+        assert_ne!(a.value, 0, "a should be non zero");
+        assert_ne!(d.value, 0, "d should be non zero");
+        assert_ne!(a, d, "a and d should be different");
+
+        let curve = Self { a, d };
+        assert!(
+            curve.is_complete(),
+            "a should be a square and d a non square"
+        );
+        curve
+    }
+
+    pub fn point(&self, x: Field, y: Field) -> EdwardsPoint {
+        EdwardsPoint { x, y, curve: *self }
+    }
+
+    pub fn identity(&self) -> EdwardsPoint {
+        self.point(self.a.zero(), self.a.one())
+    }
+
+    /// The addition law has no exceptional cases when a is a square and d is not
+    pub fn is_complete(&self) -> bool {
+        self.a.legendre_symbol() == 1 && self.d.legendre_symbol() == -1
+    }
+
+    pub fn is_on_curve(&self, point: &EdwardsPoint) -> bool {
+        let x2 = point.x * point.x;
+        let y2 = point.y * point.y;
+        self.a * x2 + y2 == x2.one() + self.d * x2 * y2
+    }
+
+    pub fn neg(&self, point: &EdwardsPoint) -> EdwardsPoint {
+        self.point(point.x.zero() - point.x, point.y)
+    }
+
+    //        x1y2 + y1x2                y1y2 - ax1x2
+    // x3 = ----------------    y3 = ----------------
+    //      1 + dx1x2y1y2            1 - dx1x2y1y2
+    //
+    // The same formula doubles, and since the curve is complete the denominators never
+    // vanish for points on it.
+    pub fn add(&self, point_a: &EdwardsPoint, point_b: &EdwardsPoint) -> EdwardsPoint {
+        let x1x2 = point_a.x * point_b.x;
+        let y1y2 = point_a.y * point_b.y;
+        let dxy = self.d * x1x2 * y1y2;
+        let one = dxy.one();
+
+        let x3 = (point_a.x * point_b.y + point_a.y * point_b.x) / (one + dxy);
+        let y3 = (y1y2 - self.a * x1x2) / (one - dxy);
+
+        self.point(x3, y3)
+    }
+
+    pub fn double(&self, point: &EdwardsPoint) -> EdwardsPoint {
+        self.add(point, point)
+    }
+
+    /// Double-and-add over a little-endian limb scalar, the complete formula needs
+    /// no special case for the identity
+    pub fn scalar_multiplication(&self, point: &EdwardsPoint, scalar: &[u64]) -> EdwardsPoint {
+        let mut result = self.identity();
+        for i in (0..scalar.len() * 64).rev() {
+            result = self.double(&result);
+            if limb_bit(scalar, i) {
+                result = self.add(&result, point);
+            }
+        }
+        result
+    }
+
+    /// The birationally equivalent Montgomery curve
+    /// A = 2(a + d) / (a - d), B = 4 / (a - d)
+    pub fn to_montgomery(&self) -> MontgomeryCurve {
+        let two = self.a.one() + self.a.one();
+        let a_minus_d = self.a - self.d;
+
+        MontgomeryCurve::new(two * (self.a + self.d) / a_minus_d, two * two / a_minus_d)
+    }
+
+    /// a = (A + 2) / B, d = (A - 2) / B, panics if that Edwards curve is not complete
+    pub fn from_montgomery(curve: &MontgomeryCurve) -> Self {
+        let two = curve.a.one() + curve.a.one();
+        Self::new((curve.a + two) / curve.b, (curve.a - two) / curve.b)
+    }
+
+    /// (x, y) -> ((1 + y) / (1 - y), (1 + y) / ((1 - y)x)),
+    /// with (0, 1) -> O and (0, -1) -> (0, 0)
+    pub fn point_to_montgomery(&self, point: &EdwardsPoint) -> MontgomeryPoint {
+        let curve = self.to_montgomery();
+        let one = point.y.one();
+
+        if point.x.value == 0 {
+            if point.y == one {
+                return curve.infinity();
+            }
+            return curve.point(point.x.zero(), point.x.zero());
+        }
+
+        let u = (one + point.y) / (one - point.y);
+        curve.point(u, u / point.x)
+    }
+
+    /// (u, v) -> (u / v, (u - 1) / (u + 1)), the inverse of `point_to_montgomery`.
+    ///
+    /// Returns `None` for v = 0 or u = -1 other than (0, 0). Such points would sit at
+    /// infinity on the Edwards model, and the Montgomery form of a complete curve has
+    /// none, so this only happens for points off the curve.
+    pub fn point_from_montgomery(&self, point: &MontgomeryPoint) -> Option<EdwardsPoint> {
+        if point.is_infinity {
+            return Some(self.identity());
+        }
+
+        let one = point.x.one();
+        if point.x.value == 0 && point.y.value == 0 {
+            return Some(self.point(point.x.zero(), point.x.zero() - one));
+        }
+
+        let v_inv = point.y.inverse()?;
+        let u_plus_one_inv = (point.x + one).inverse()?;
+
+        Some(self.point(point.x * v_inv, (point.x - one) * u_plus_one_inv))
+    }
+
+    /// The birationally equivalent short Weierstrass curve, through the Montgomery form
+    pub fn to_weierstrass(&self) -> EllipticCurve {
+        self.to_montgomery().to_weierstrass()
+    }
+
+    pub fn point_to_weierstrass(&self, point: &EdwardsPoint) -> ECPoint {
+        let montgomery = self.to_montgomery();
+        montgomery.point_to_weierstrass(&self.point_to_montgomery(point))
+    }
+
+    pub fn point_from_weierstrass(&self, point: &ECPoint) -> Option<EdwardsPoint> {
+        let montgomery = self.to_montgomery();
+        self.point_from_montgomery(&montgomery.point_from_weierstrass(point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::short_weierstras::EllipticCurveTrait;
+
+    const P: usize = 1009;
+
+    fn setup_curve() -> TwistedEdwardsCurve {
+        // -x^2 + y^2 = 1 + dx^2y^2, Ed25519 shaped: -1 is a square as P = 1 mod 4
+        let d = (2..P)
+            .map(|d| Field::new(d, P))
+            .find(|d| d.legendre_symbol() == -1)
+            .unwrap();
+        TwistedEdwardsCurve::new(Field::new(P - 1, P), d)
+    }
+
+    fn sample_points(curve: &TwistedEdwardsCurve, count: usize) -> Vec<EdwardsPoint> {
+        // y^2 = (1 - ax^2) / (1 - dx^2)
+        (0..P)
+            .filter_map(|x| {
+                let x = Field::new(x, P);
+                let one = x.one();
+                let y = ((one - curve.a * x * x) / (one - curve.d * x * x)).sqrt()?;
+                Some(curve.point(x, y))
+            })
+            .take(count)
+            .collect()
+    }
+
+    #[test]
+    fn test_complete_addition() {
+        let curve = setup_curve();
+        assert!(curve.is_complete());
+
+        let identity = curve.identity();
+        let points = sample_points(&curve, 25);
+
+        for p in points.iter() {
+            assert!(curve.is_on_curve(p));
+            assert_eq!(curve.add(p, &identity), *p);
+            assert_eq!(curve.add(p, &curve.neg(p)), identity);
+
+            for q in points.iter() {
+                let sum = curve.add(p, q);
+                assert!(curve.is_on_curve(&sum));
+                assert_eq!(sum, curve.add(q, p));
+
+                // (p + q) + p = p + (q + p)
+                assert_eq!(curve.add(&sum, p), curve.add(p, &curve.add(q, p)));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "a should be a square and d a non square")]
+    fn test_incomplete_curve() {
+        // d = 4 is a square, so 1 +- dx1x2y1y2 vanishes for some pairs of points
+        let _ = TwistedEdwardsCurve::new(Field::new(P - 1, P), Field::new(4, P));
+    }
+
+    #[test]
+    fn test_scalar_multiplication() {
+        let curve = setup_curve();
+        let point = sample_points(&curve, 3)[2];
+
+        let mut expected = curve.identity();
+        for k in 0..50u64 {
+            assert_eq!(curve.scalar_multiplication(&point, &[k]), expected);
+            expected = curve.add(&expected, &point);
+        }
+    }
+
+    #[test]
+    fn test_montgomery_map() {
+        let curve = setup_curve();
+        let montgomery = curve.to_montgomery();
+        assert_eq!(TwistedEdwardsCurve::from_montgomery(&montgomery), curve);
+
+        let points = sample_points(&curve, 25);
+        for p in points.iter() {
+            let p_m = curve.point_to_montgomery(p);
+            assert!(montgomery.is_on_curve(&p_m));
+            assert_eq!(curve.point_from_montgomery(&p_m), Some(*p));
+
+            for q in points.iter() {
+                let sum = curve.point_to_montgomery(&curve.add(p, q));
+                let q_m = curve.point_to_montgomery(q);
+                assert_eq!(sum, montgomery.add(&p_m, &q_m));
+            }
+        }
+
+        // the identity and the point of order 2
+        let order_two = curve.point(Field::new(0, P), Field::new(P - 1, P));
+        assert!(curve.point_to_montgomery(&curve.identity()).is_infinity);
+        assert_eq!(
+            curve.point_from_montgomery(&curve.point_to_montgomery(&order_two)),
+            Some(order_two)
+        );
+    }
+
+    #[test]
+    fn test_weierstrass_map() {
+        let curve = setup_curve();
+        let weierstrass = curve.to_weierstrass();
+        let points = sample_points(&curve, 25);
+
+        for p in points.iter() {
+            let p_w = curve.point_to_weierstrass(p);
+            assert!(weierstrass.is_on_curve(&p_w));
+            assert_eq!(curve.point_from_weierstrass(&p_w), Some(*p));
+
+            for q in points.iter() {
+                let q_w = curve.point_to_weierstrass(q);
+                // the affine Weierstrass formulas can't handle P + (-P) or 2 * (x, 0)
+                if p_w.x == q_w.x || p_w.is_infinity || q_w.is_infinity {
+                    continue;
+                }
+                let sum = weierstrass.add(&p_w, &q_w).unwrap();
+                assert_eq!(curve.point_to_weierstrass(&curve.add(p, q)), sum);
+            }
+        }
+    }
+}
@@ -1,3 +1,6 @@
+use crate::short_weierstras::CurveField;
+use field::utils::shift_right_one;
+
 pub fn bits(scalar: usize) -> usize {
     if scalar == 0 {
         return 0;
@@ -21,3 +24,134 @@ pub fn bit(scalar: usize, index: usize) -> bool {
         false
     }
 }
+
+/// 2 * value as an addition, which the coordinate formulas use instead of multiplications
+pub fn double<F: CurveField>(value: F) -> F {
+    value + value
+}
+
+/// Returns whether the bit in the given position of a little-endian limb scalar is set
+pub fn limb_bit(scalar: &[u64], index: usize) -> bool {
+    let digit_index = index / 64;
+    let bit_offset = index % 64;
+
+    match scalar.get(digit_index) {
+        Some(limb) => (limb >> bit_offset) & 1 == 1,
+        None => false,
+    }
+}
+
+/// Width-w non-adjacent form of a little-endian limb scalar, least significant digit first.
+///
+/// Every non-zero digit is odd and lies in (-2^(w-1), 2^(w-1)), and any w consecutive
+/// digits contain at most one non-zero digit.
+pub fn wnaf(scalar: &[u64], window: usize) -> Vec<i64> {
+    assert!(
+        (2..=32).contains(&window),
+        "Window should be between 2 and 32"
+    );
+
+    // one spare limb: adding |digit| back can carry past the top limb
+    let mut k = scalar.to_vec();
+    k.push(0);
+
+    let modulus = 1i64 << window;
+    let mut digits = vec![];
+
+    while k.iter().any(|limb| *limb != 0) {
+        let mut digit = 0;
+        if k[0] & 1 == 1 {
+            digit = (k[0] & (modulus as u64 - 1)) as i64;
+            if digit >= modulus / 2 {
+                digit -= modulus;
+            }
+
+            if digit > 0 {
+                sub_small(&mut k, digit as u64);
+            } else {
+                add_small(&mut k, digit.unsigned_abs());
+            }
+        }
+        digits.push(digit);
+        shift_right_one(&mut k);
+    }
+
+    digits
+}
+
+fn add_small(limbs: &mut [u64], value: u64) {
+    let mut carry = value;
+    for limb in limbs.iter_mut() {
+        let (sum, overflow) = limb.overflowing_add(carry);
+        *limb = sum;
+        if !overflow {
+            return;
+        }
+        carry = 1;
+    }
+}
+
+fn sub_small(limbs: &mut [u64], value: u64) {
+    let mut borrow = value;
+    for limb in limbs.iter_mut() {
+        let (difference, underflow) = limb.overflowing_sub(borrow);
+        *limb = difference;
+        if !underflow {
+            return;
+        }
+        borrow = 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // sum of digit_i * 2^i, only for scalars that fit in an i128
+    fn evaluate(digits: &[i64]) -> i128 {
+        digits
+            .iter()
+            .rev()
+            .fold(0i128, |acc, digit| 2 * acc + *digit as i128)
+    }
+
+    #[test]
+    fn test_limb_bit() {
+        let scalar = [0b101, 1];
+        assert!(limb_bit(&scalar, 0));
+        assert!(!limb_bit(&scalar, 1));
+        assert!(limb_bit(&scalar, 2));
+        assert!(limb_bit(&scalar, 64));
+        assert!(!limb_bit(&scalar, 65));
+        assert!(!limb_bit(&scalar, 200));
+    }
+
+    #[test]
+    fn test_wnaf() {
+        for window in 2..6 {
+            for k in (0..1000u64).chain([u64::MAX, u64::MAX - 1]) {
+                let digits = wnaf(&[k], window);
+                assert_eq!(evaluate(&digits), k as i128);
+
+                for (i, digit) in digits.iter().enumerate() {
+                    if *digit != 0 {
+                        assert_eq!(digit.rem_euclid(2), 1);
+                        assert!(digit.abs() < 1 << (window - 1));
+                        let next = &digits[i + 1..(i + window).min(digits.len())];
+                        assert!(next.iter().all(|d| *d == 0));
+                    }
+                }
+            }
+        }
+
+        // carries across limbs: 2^126 - 1
+        let digits = wnaf(&[u64::MAX, u64::MAX >> 2], 4);
+        assert_eq!(evaluate(&digits), (1i128 << 126) - 1);
+
+        // 2^128 - 1 needs a digit past the top limb
+        let digits = wnaf(&[u64::MAX, u64::MAX], 4);
+        assert_eq!(digits.len(), 129);
+        assert_eq!(digits[0], -1);
+        assert_eq!(digits[128], 1);
+    }
+}
@@ -0,0 +1,504 @@
+use crate::{
+    field::FieldTrait,
+    utils::{pow_limbs, shift_right_one, tonelli_shanks},
+};
+use std::ops::{Add, Div, Mul, Sub};
+
+/// A 256-bit prime field element, e.g. the secp256k1 or BN254 base fields
+pub type Field256 = BigField<4>;
+/// A 384-bit prime field element, e.g. the BLS12-381 base field
+pub type Field384 = BigField<6>;
+
+/// Precomputed constants for Montgomery arithmetic modulo an odd `p`
+/// stored as `N` little-endian 64-bit limbs, with R = 2^(64 * N).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MontgomeryParams<const N: usize> {
+    modulus: [u64; N],
+    // R^2 mod p, used to move values into Montgomery form
+    r2: [u64; N],
+    // -p^(-1) mod 2^64
+    inv: u64,
+}
+
+/// A prime field element with a multi-limb modulus. The value is kept in
+/// Montgomery form (a * R mod p) so that multiplication never needs a division.
+#[derive(Debug, Clone, Copy)]
+pub struct BigField<const N: usize> {
+    value: [u64; N],
+    params: MontgomeryParams<N>,
+}
+
+impl<const N: usize> MontgomeryParams<N> {
+    pub fn new(modulus: [u64; N]) -> Self {
+        assert!(N > 0, "Modulus should have at least one limb");
+        assert!(modulus[0] & 1 == 1, "Modulus should be odd");
+        assert!(!is_one(&modulus), "Modulus should be greater than 1");
+
+        // Newton iteration doubles the number of correct low bits each round:
+        // 1 -> 2 -> 4 -> ... -> 64
+        let mut inv = 1u64;
+        for _ in 0..6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(modulus[0].wrapping_mul(inv)));
+        }
+
+        // R^2 mod p = 2^(128 * N) mod p, by repeated doubling of 1
+        let mut r2 = [0u64; N];
+        r2[0] = 1;
+        for _ in 0..128 * N {
+            r2 = double_mod(&r2, &modulus);
+        }
+
+        Self {
+            modulus,
+            r2,
+            inv: inv.wrapping_neg(),
+        }
+    }
+
+    pub fn from_hex(hex: &str) -> Self {
+        Self::new(limbs_from_hex(hex))
+    }
+
+    pub fn modulus(&self) -> [u64; N] {
+        self.modulus
+    }
+
+    /// x * y * R^(-1) mod p (CIOS Montgomery multiplication)
+    fn mont_mul(&self, x: &[u64; N], y: &[u64; N]) -> [u64; N] {
+        let mut t = [0u64; N];
+        let mut t_hi = 0u64;
+
+        for &y_i in y.iter() {
+            // t += x * y_i
+            let mut carry = 0;
+            for (t_j, &x_j) in t.iter_mut().zip(x.iter()) {
+                (*t_j, carry) = mac(*t_j, x_j, y_i, carry);
+            }
+            let (hi, extra) = adc(t_hi, carry, 0);
+
+            // t = (t + m * p) / 2^64, where m makes the lowest limb vanish
+            let m = t[0].wrapping_mul(self.inv);
+            let (_, mut carry) = mac(t[0], m, self.modulus[0], 0);
+            for j in 1..N {
+                (t[j - 1], carry) = mac(t[j], m, self.modulus[j], carry);
+            }
+            let (lo, c) = adc(hi, carry, 0);
+            t[N - 1] = lo;
+            t_hi = extra + c;
+        }
+
+        if t_hi != 0 || geq(&t, &self.modulus) {
+            t = sub_limbs(&t, &self.modulus).0;
+        }
+
+        t
+    }
+
+    fn montgomery_form(&self, value: &[u64; N]) -> [u64; N] {
+        self.mont_mul(value, &self.r2)
+    }
+
+    fn canonical_form(&self, value: &[u64; N]) -> [u64; N] {
+        let mut one = [0u64; N];
+        one[0] = 1;
+        self.mont_mul(value, &one)
+    }
+}
+
+impl<const N: usize> BigField<N> {
+    /// Creates a field element from little-endian limbs, reducing them modulo p
+    pub fn new(value: [u64; N], params: MontgomeryParams<N>) -> Self {
+        Self {
+            value: params.montgomery_form(&value),
+            params,
+        }
+    }
+
+    pub fn from_u64(value: u64, params: MontgomeryParams<N>) -> Self {
+        let mut limbs = [0u64; N];
+        limbs[0] = value;
+        Self::new(limbs, params)
+    }
+
+    pub fn from_hex(hex: &str, params: MontgomeryParams<N>) -> Self {
+        Self::new(limbs_from_hex(hex), params)
+    }
+
+    /// Returns the canonical (non-Montgomery) little-endian limbs of the element
+    pub fn to_limbs(&self) -> [u64; N] {
+        self.params.canonical_form(&self.value)
+    }
+
+    pub fn params(&self) -> MontgomeryParams<N> {
+        self.params
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value.iter().all(|limb| *limb == 0)
+    }
+
+    fn p_minus_one(&self) -> [u64; N] {
+        let mut one = [0u64; N];
+        one[0] = 1;
+        sub_limbs(&self.params.modulus, &one).0
+    }
+
+    /// Raises the element to an exponent given as little-endian limbs
+    pub fn pow_limbs(&self, exponent: &[u64]) -> Self {
+        pow_limbs(*self, self.one(), exponent)
+    }
+}
+
+impl<const N: usize> FieldTrait for BigField<N> {
+    type Modulus = [u64; N];
+
+    fn modulus(&self) -> [u64; N] {
+        self.params.modulus
+    }
+
+    /// a^(-1) = a^(p - 2) mod p (Fermat's little theorem)
+    fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+
+        let mut two = [0u64; N];
+        two[0] = 2;
+        let (p_minus_two, _) = sub_limbs(&self.params.modulus, &two);
+
+        Some(self.pow_limbs(&p_minus_two))
+    }
+
+    fn pow(&self, exponent: usize) -> Self {
+        self.pow_limbs(&[exponent as u64])
+    }
+
+    /// Tonelli-Shanks square root, returns `None` for quadratic non-residues
+    fn sqrt(&self) -> Option<Self> {
+        if self.is_zero() {
+            return Some(*self);
+        }
+
+        if self.legendre_symbol() != 1 {
+            return None;
+        }
+
+        let one = self.one();
+        let p_minus_one = self.p_minus_one();
+        let mut half = p_minus_one;
+        shift_right_one(&mut half);
+
+        // any quadratic non-residue z
+        let mut z = one + one;
+        while z.pow_limbs(&half) == one {
+            z = z + one;
+        }
+
+        tonelli_shanks(*self, z, one, &p_minus_one)
+    }
+
+    /// Euler's criterion: a^((p - 1) / 2) is 1 for squares, -1 for non-squares
+    /// and 0 for zero
+    fn legendre_symbol(&self) -> i8 {
+        if self.is_zero() {
+            return 0;
+        }
+
+        let mut half = self.p_minus_one();
+        shift_right_one(&mut half);
+        if self.pow_limbs(&half) == self.one() {
+            1
+        } else {
+            -1
+        }
+    }
+
+    fn zero(&self) -> Self {
+        Self {
+            value: [0u64; N],
+            params: self.params,
+        }
+    }
+
+    fn one(&self) -> Self {
+        Self::from_u64(1, self.params)
+    }
+}
+
+impl<const N: usize> Add for BigField<N> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        assert_eq!(
+            self.params, other.params,
+            "Add Operation should be within the same Field"
+        );
+
+        let (sum, carry) = add_limbs(&self.value, &other.value);
+        let value = if carry == 1 || geq(&sum, &self.params.modulus) {
+            sub_limbs(&sum, &self.params.modulus).0
+        } else {
+            sum
+        };
+
+        Self {
+            value,
+            params: self.params,
+        }
+    }
+}
+
+impl<const N: usize> Sub for BigField<N> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        assert_eq!(
+            self.params, other.params,
+            "Sub Operation should be within the same Field"
+        );
+
+        let (difference, borrow) = sub_limbs(&self.value, &other.value);
+        let value = if borrow == 1 {
+            add_limbs(&difference, &self.params.modulus).0
+        } else {
+            difference
+        };
+
+        Self {
+            value,
+            params: self.params,
+        }
+    }
+}
+
+impl<const N: usize> Mul for BigField<N> {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        assert_eq!(
+            self.params, other.params,
+            "Mul Operation should be within the same Field"
+        );
+
+        Self {
+            value: self.params.mont_mul(&self.value, &other.value),
+            params: self.params,
+        }
+    }
+}
+
+impl<const N: usize> Div for BigField<N> {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        assert_eq!(
+            self.params, other.params,
+            "Div Operation should be within the same Field"
+        );
+        assert!(!other.is_zero(), "Division by zero");
+
+        let inverse = other.inverse().expect("No multiplicative inverse exists");
+        self * inverse
+    }
+}
+
+impl<const N: usize> PartialEq for BigField<N> {
+    fn eq(&self, other: &Self) -> bool {
+        assert_eq!(
+            self.params, other.params,
+            "You can only compare between same Field"
+        );
+        self.value == other.value
+    }
+}
+
+/// Parses a big-endian hex string (optionally `0x` prefixed) into little-endian limbs
+pub fn limbs_from_hex<const N: usize>(hex: &str) -> [u64; N] {
+    let hex = hex.trim_start_matches("0x");
+    assert!(hex.len() <= 16 * N, "Hex value does not fit in {} limbs", N);
+
+    let mut limbs = [0u64; N];
+    let mut end = hex.len();
+    for limb in limbs.iter_mut() {
+        if end == 0 {
+            break;
+        }
+        let start = end.saturating_sub(16);
+        *limb = u64::from_str_radix(&hex[start..end], 16).expect("Invalid hex value");
+        end = start;
+    }
+
+    limbs
+}
+
+// a + b + carry, returning (result, carry)
+fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+// a - b - borrow, returning (result, borrow)
+fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = (a as u128).wrapping_sub(b as u128 + borrow as u128);
+    (t as u64, (t >> 127) as u64)
+}
+
+// a + b * c + carry, returning (result, carry)
+fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + (b as u128 * c as u128) + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+fn add_limbs<const N: usize>(a: &[u64; N], b: &[u64; N]) -> ([u64; N], u64) {
+    let mut result = [0u64; N];
+    let mut carry = 0;
+    for i in 0..N {
+        (result[i], carry) = adc(a[i], b[i], carry);
+    }
+    (result, carry)
+}
+
+fn sub_limbs<const N: usize>(a: &[u64; N], b: &[u64; N]) -> ([u64; N], u64) {
+    let mut result = [0u64; N];
+    let mut borrow = 0;
+    for i in 0..N {
+        (result[i], borrow) = sbb(a[i], b[i], borrow);
+    }
+    (result, borrow)
+}
+
+fn geq<const N: usize>(a: &[u64; N], b: &[u64; N]) -> bool {
+    for i in (0..N).rev() {
+        if a[i] != b[i] {
+            return a[i] > b[i];
+        }
+    }
+    true
+}
+
+fn is_one<const N: usize>(a: &[u64; N]) -> bool {
+    a[0] == 1 && a[1..].iter().all(|limb| *limb == 0)
+}
+
+// 2a mod p, assuming a < p
+fn double_mod<const N: usize>(a: &[u64; N], modulus: &[u64; N]) -> [u64; N] {
+    let (doubled, carry) = add_limbs(a, a);
+    if carry == 1 || geq(&doubled, modulus) {
+        sub_limbs(&doubled, modulus).0
+    } else {
+        doubled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::Field;
+
+    const SECP256K1_P: &str = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f";
+    const BLS12_381_P: &str = "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab";
+    const BLS12_381_R: &str = "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+
+    #[test]
+    fn test_matches_small_field() {
+        let modulus = 65537;
+        let params = MontgomeryParams::<1>::new([modulus]);
+
+        for (a, b) in [(3, 5), (65536, 65536), (12345, 54321), (0, 7), (40000, 1)] {
+            let big_a = BigField::from_u64(a, params);
+            let big_b = BigField::from_u64(b, params);
+            let small_a = Field::new(a as usize, modulus as usize);
+            let small_b = Field::new(b as usize, modulus as usize);
+
+            assert_eq!(
+                (big_a + big_b).to_limbs()[0] as usize,
+                (small_a + small_b).value
+            );
+            assert_eq!(
+                (big_a - big_b).to_limbs()[0] as usize,
+                (small_a - small_b).value
+            );
+            assert_eq!(
+                (big_a * big_b).to_limbs()[0] as usize,
+                (small_a * small_b).value
+            );
+            assert_eq!(big_a.pow(13).to_limbs()[0] as usize, small_a.pow(13).value);
+        }
+    }
+
+    #[test]
+    fn test_secp256k1_generator_is_on_curve() {
+        // y^2 = x^3 + 7
+        let params = MontgomeryParams::<4>::from_hex(SECP256K1_P);
+        let x = Field256::from_hex(
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            params,
+        );
+        let y = Field256::from_hex(
+            "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+            params,
+        );
+        let seven = Field256::from_u64(7, params);
+
+        assert_eq!(y * y, x * x * x + seven);
+        assert_eq!(x.modulus(), limbs_from_hex(SECP256K1_P));
+    }
+
+    #[test]
+    fn test_bls12_381_generator_is_on_curve() {
+        // y^2 = x^3 + 4
+        let params = MontgomeryParams::<6>::from_hex(BLS12_381_P);
+        let x = Field384::from_hex(
+            "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb",
+            params,
+        );
+        let y = Field384::from_hex(
+            "08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1",
+            params,
+        );
+        let four = Field384::from_u64(4, params);
+
+        assert_eq!(y * y, x.pow(3) + four);
+    }
+
+    #[test]
+    fn test_inverse_and_div() {
+        let params = MontgomeryParams::<4>::from_hex(SECP256K1_P);
+        let a = Field256::from_hex("deadbeefcafebabe0123456789abcdef", params);
+        let b = Field256::from_u64(42, params);
+
+        assert_eq!(a * a.inverse().unwrap(), a.one());
+        assert_eq!((a / b) * b, a);
+        assert!(a.zero().inverse().is_none());
+        assert_eq!(a - a, a.zero());
+        assert_eq!(a.zero() - b + b, a.zero());
+    }
+
+    #[test]
+    fn test_reduces_values_above_modulus() {
+        let params = MontgomeryParams::<4>::from_hex(SECP256K1_P);
+        let p = Field256::new(limbs_from_hex(SECP256K1_P), params);
+        let p_plus_one = Field256::new([0xfffffffefffffc30, u64::MAX, u64::MAX, u64::MAX], params);
+
+        assert!(p.is_zero());
+        assert_eq!(p_plus_one, p.one());
+    }
+
+    #[test]
+    fn test_sqrt() {
+        // p = 3 mod 4
+        let params = MontgomeryParams::<6>::from_hex(BLS12_381_P);
+        let a = Field384::from_hex("123456789abcdef0fedcba9876543210", params);
+        let root = (a * a).sqrt().unwrap();
+        assert!(root == a || root == a.zero() - a);
+
+        // p = 1 mod 4 with 2-adicity 32
+        let params = MontgomeryParams::<4>::from_hex(BLS12_381_R);
+        let a = Field256::from_hex("0f0e0d0c0b0a09080706050403020100", params);
+        let root = (a * a).sqrt().unwrap();
+        assert_eq!(root * root, a * a);
+
+        // 7 generates the multiplicative group, so it can't be a square
+        let seven = Field256::from_u64(7, params);
+        assert!(seven.sqrt().is_none());
+        assert_eq!(seven.legendre_symbol(), -1);
+        assert!((seven * seven).is_square());
+        assert_eq!(seven.zero().legendre_symbol(), 0);
+    }
+}
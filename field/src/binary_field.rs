@@ -0,0 +1,296 @@
+use crate::{field::FieldTrait, utils::prime_factors};
+use std::ops::{Add, Div, Mul, Sub};
+
+/// An element of GF(2^k) = GF(2)[x] / (f(x)), stored as a bit vector where bit `i`
+/// is the coefficient of x^i. The defining polynomial `f` (including its x^k term)
+/// is kept alongside the value, so `k` can be anything from 1 to 63.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BinaryField {
+    pub value: u64,
+    modulus: u64,
+}
+
+impl BinaryField {
+    pub fn new(value: u64, modulus: u64) -> Self {
+        assert!(modulus > 1, "Modulus should have degree at least 1");
+        Self {
+            value: reduce(value as u128, modulus),
+            modulus,
+        }
+    }
+
+    /// The extension degree k
+    pub fn degree(&self) -> u32 {
+        degree(self.modulus as u128)
+    }
+
+    /// a -> a^2, the generator of Gal(GF(2^k) / GF(2))
+    pub fn frobenius(&self) -> Self {
+        *self * *self
+    }
+
+    /// Rabin's irreducibility test over GF(2): f of degree k is irreducible iff
+    /// x^(2^k) = x mod f and gcd(x^(2^(k/r)) - x, f) = 1 for every prime r | k
+    pub fn is_irreducible(modulus: u64) -> bool {
+        if modulus < 2 {
+            return false;
+        }
+
+        let k = degree(modulus as u128);
+        let x = Self::new(0b10, modulus);
+
+        // x^(2^j) mod f
+        let x_pow = |j: u32| (0..j).fold(x, |acc, _| acc.frobenius());
+
+        if x_pow(k) != x {
+            return false;
+        }
+
+        prime_factors(k as usize).into_iter().all(|r| {
+            let h = (x_pow(k / r as u32) - x).value;
+            gcd(modulus, h) == 1
+        })
+    }
+}
+
+impl FieldTrait for BinaryField {
+    type Modulus = u64;
+
+    fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// a^(-1) = a^(2^k - 2)
+    fn inverse(&self) -> Option<Self> {
+        if self.value == 0 {
+            return None;
+        }
+
+        Some(self.pow_u64((1u64 << self.degree()) - 2))
+    }
+
+    fn pow(&self, exponent: usize) -> Self {
+        self.pow_u64(exponent as u64)
+    }
+
+    /// Squaring is a bijection in characteristic 2, sqrt(a) = a^(2^(k - 1))
+    fn sqrt(&self) -> Option<Self> {
+        Some((1..self.degree()).fold(*self, |acc, _| acc.frobenius()))
+    }
+
+    fn legendre_symbol(&self) -> i8 {
+        if self.value == 0 {
+            0
+        } else {
+            1
+        }
+    }
+
+    fn zero(&self) -> Self {
+        BinaryField::new(0, self.modulus)
+    }
+
+    fn one(&self) -> Self {
+        BinaryField::new(1, self.modulus)
+    }
+}
+
+impl BinaryField {
+    fn pow_u64(&self, exponent: u64) -> Self {
+        let mut result = self.one();
+        let mut base = *self;
+        let mut exp = exponent;
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result * base;
+            }
+            exp >>= 1;
+            base = base * base;
+        }
+
+        result
+    }
+}
+
+impl Add for BinaryField {
+    type Output = Self;
+    fn add(self, other: BinaryField) -> Self {
+        assert_eq!(
+            self.modulus, other.modulus,
+            "Add Operation should be within the same Field"
+        );
+        BinaryField {
+            value: self.value ^ other.value,
+            modulus: self.modulus,
+        }
+    }
+}
+
+impl Sub for BinaryField {
+    type Output = Self;
+    fn sub(self, other: BinaryField) -> Self {
+        assert_eq!(
+            self.modulus, other.modulus,
+            "Sub Operation should be within the same Field"
+        );
+        BinaryField {
+            value: self.value ^ other.value,
+            modulus: self.modulus,
+        }
+    }
+}
+
+impl Mul for BinaryField {
+    type Output = Self;
+    fn mul(self, other: BinaryField) -> Self {
+        assert_eq!(
+            self.modulus, other.modulus,
+            "Mul Operation should be within the same Field"
+        );
+        BinaryField {
+            value: reduce(clmul(self.value, other.value), self.modulus),
+            modulus: self.modulus,
+        }
+    }
+}
+
+impl Div for BinaryField {
+    type Output = Self;
+    fn div(self, other: BinaryField) -> Self {
+        assert_eq!(
+            self.modulus, other.modulus,
+            "Div Operation should be within the same Field"
+        );
+        assert_ne!(other.value, 0, "Division by zero");
+
+        let inverse = other.inverse().expect("No multiplicative inverse exists");
+        self * inverse
+    }
+}
+
+impl PartialEq for BinaryField {
+    fn eq(&self, other: &Self) -> bool {
+        assert_eq!(
+            self.modulus, other.modulus,
+            "You can only compare between same Field"
+        );
+        self.value == other.value
+    }
+}
+
+/// Carry-less (GF(2)[x]) multiplication
+pub fn clmul(a: u64, b: u64) -> u128 {
+    let mut result = 0u128;
+    for i in 0..64 {
+        if (b >> i) & 1 == 1 {
+            result ^= (a as u128) << i;
+        }
+    }
+    result
+}
+
+fn degree(poly: u128) -> u32 {
+    127 - poly.leading_zeros()
+}
+
+// remainder of a GF(2)[x] division
+fn reduce(value: u128, modulus: u64) -> u64 {
+    let modulus_degree = degree(modulus as u128);
+    let mut value = value;
+
+    while value != 0 && degree(value) >= modulus_degree {
+        value ^= (modulus as u128) << (degree(value) - modulus_degree);
+    }
+
+    value as u64
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    let (mut a, mut b) = (a, b);
+    while b != 0 {
+        (a, b) = (b, reduce(a as u128, b));
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // x^8 + x^4 + x^3 + x + 1, the AES polynomial
+    const AES: u64 = 0x11b;
+
+    #[test]
+    fn test_aes_multiplication() {
+        // FIPS-197 section 4.2: {57} * {83} = {c1}, {57} * {13} = {fe}
+        let a = BinaryField::new(0x57, AES);
+        assert_eq!(a * BinaryField::new(0x83, AES), BinaryField::new(0xc1, AES));
+        assert_eq!(a * BinaryField::new(0x13, AES), BinaryField::new(0xfe, AES));
+        assert_eq!(a + BinaryField::new(0x83, AES), BinaryField::new(0xd4, AES));
+        assert_eq!(a - a, a.zero());
+        assert_eq!(a.degree(), 8);
+    }
+
+    #[test]
+    fn test_inverse() {
+        for value in 1..256 {
+            let a = BinaryField::new(value, AES);
+            assert_eq!(a * a.inverse().unwrap(), a.one());
+            assert_eq!(a / a, a.one());
+        }
+        assert!(BinaryField::new(0, AES).inverse().is_none());
+        // {53}^(-1) = {ca}
+        assert_eq!(
+            BinaryField::new(0x53, AES).inverse().unwrap(),
+            BinaryField::new(0xca, AES)
+        );
+    }
+
+    #[test]
+    fn test_frobenius() {
+        for value in 0..256 {
+            let a = BinaryField::new(value, AES);
+            let b = BinaryField::new(value ^ 0x5a, AES);
+
+            // a^(2^8) = a
+            let orbit = (0..8).fold(a, |acc, _| acc.frobenius());
+            assert_eq!(orbit, a);
+
+            // (a + b)^2 = a^2 + b^2
+            assert_eq!((a + b).frobenius(), a.frobenius() + b.frobenius());
+
+            let root = a.sqrt().unwrap();
+            assert_eq!(root * root, a);
+        }
+
+        // the fixed points of the frobenius map are exactly GF(2)
+        let fixed = (0..256)
+            .filter(|v| BinaryField::new(*v, AES).frobenius() == BinaryField::new(*v, AES))
+            .count();
+        assert_eq!(fixed, 2);
+    }
+
+    #[test]
+    fn test_is_irreducible() {
+        assert!(BinaryField::is_irreducible(AES));
+        // x^2 + x + 1
+        assert!(BinaryField::is_irreducible(0b111));
+        // x^64 would not fit, x^63 + x + 1 is irreducible
+        assert!(BinaryField::is_irreducible((1 << 63) | 0b11));
+        // x^2 + 1 = (x + 1)^2
+        assert!(!BinaryField::is_irreducible(0b101));
+        // x^4 + x^2 + 1 = (x^2 + x + 1)^2 has no roots but is reducible
+        assert!(!BinaryField::is_irreducible(0b10101));
+        // x^8 + x^4 + x^3 + x^2 + 1 is irreducible (primitive, used by Reed-Solomon)
+        assert!(BinaryField::is_irreducible(0x11d));
+    }
+
+    #[test]
+    fn test_large_degree() {
+        let modulus = (1 << 63) | 0b11;
+        let a = BinaryField::new(0x0123_4567_89ab_cdef, modulus);
+        assert_eq!(a * a.inverse().unwrap(), a.one());
+        assert_eq!(a.sqrt().unwrap().pow(2), a);
+    }
+}
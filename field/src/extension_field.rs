@@ -0,0 +1,600 @@
+use crate::{
+    binary_field::BinaryField,
+    field::Field,
+    field::{FieldError, FieldTrait},
+    small_prime_field::SmallPrimeField,
+    utils::{pow_limbs, prime_factors, shift_right_one, tonelli_shanks},
+};
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// A finite field that can be extended by `ExtensionField`
+pub trait BaseField:
+    FieldTrait + Copy + PartialEq + Debug + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    /// Number of elements in the field
+    fn order(&self) -> u64;
+}
+
+impl BaseField for Field {
+    fn order(&self) -> u64 {
+        self.modulus() as u64
+    }
+}
+
+impl<const P: u64> BaseField for SmallPrimeField<P> {
+    fn order(&self) -> u64 {
+        P
+    }
+}
+
+impl BaseField for BinaryField {
+    fn order(&self) -> u64 {
+        1 << self.degree()
+    }
+}
+
+/// An element c_0 + c_1 x + ... + c_(K-1) x^(K-1) of F[x] / (f(x)), where
+/// f(x) = x^K + m_(K-1) x^(K-1) + ... + m_0 is a monic irreducible polynomial
+/// over the base field F. Only the lower coefficients m_i of f are stored.
+#[derive(Debug, Clone, Copy)]
+pub struct ExtensionField<F, const K: usize> {
+    pub coefficients: [F; K],
+    modulus: [F; K],
+}
+
+impl<F: BaseField, const K: usize> ExtensionField<F, K> {
+    /// Does not check that the modulus is irreducible, see `try_new`
+    pub fn new(coefficients: [F; K], modulus: [F; K]) -> Self {
+        assert!(K > 0, "Extension degree should be at least 1");
+        Self {
+            coefficients,
+            modulus,
+        }
+    }
+
+    /// Like `new`, but rejects a reducible modulus, for which F[x] / (f(x)) is not a field
+    pub fn try_new(coefficients: [F; K], modulus: [F; K]) -> Result<Self, FieldError> {
+        if !Self::is_irreducible(modulus) {
+            return Err(FieldError::ReducibleModulus);
+        }
+        Ok(Self::new(coefficients, modulus))
+    }
+
+    /// Embeds a base field element as a constant polynomial
+    pub fn from_base(value: F, modulus: [F; K]) -> Self {
+        let mut coefficients = [value.zero(); K];
+        coefficients[0] = value;
+        Self::new(coefficients, modulus)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        let zero = self.coefficients[0].zero();
+        self.coefficients.iter().all(|c| *c == zero)
+    }
+
+    /// a -> a^q where q is the order of the base field, the generator of Gal(F_q^K / F_q)
+    pub fn frobenius(&self) -> Self {
+        self.pow_limbs(&[self.coefficients[0].order()])
+    }
+
+    /// Raises the element to an exponent given as little-endian limbs
+    pub fn pow_limbs(&self, exponent: &[u64]) -> Self {
+        pow_limbs(*self, self.one(), exponent)
+    }
+
+    /// q^K - 1, the order of the multiplicative group, as little-endian limbs
+    fn group_order(&self) -> Vec<u64> {
+        let q = self.coefficients[0].order();
+        let mut order = vec![1u64];
+
+        for _ in 0..K {
+            let mut carry = 0u128;
+            for limb in order.iter_mut() {
+                let product = *limb as u128 * q as u128 + carry;
+                *limb = product as u64;
+                carry = product >> 64;
+            }
+            if carry > 0 {
+                order.push(carry as u64);
+            }
+        }
+
+        // q^K > 0, so the borrow stops at its lowest non-zero limb, past any zero limbs
+        // when q^K is a multiple of 2^64, e.g. GF(2^8) with K = 8
+        let mut i = 0;
+        while order[i] == 0 {
+            order[i] = u64::MAX;
+            i += 1;
+        }
+        order[i] -= 1;
+
+        order
+    }
+
+    /// The `index`-th element in base-q digit order, used to enumerate candidates
+    fn element_from_index(&self, index: u64) -> Self {
+        let q = self.coefficients[0].order();
+        let one = self.coefficients[0].one();
+        let mut coefficients = [one.zero(); K];
+        let mut rest = index;
+
+        for coefficient in coefficients.iter_mut() {
+            let digit = rest % q;
+            rest /= q;
+            // digit * 1 by repeated addition keeps this generic over the base field
+            *coefficient = (0..digit).fold(one.zero(), |acc, _| acc + one);
+        }
+
+        Self::new(coefficients, self.modulus)
+    }
+
+    /// Rabin's irreducibility test: f of degree K is irreducible over F_q iff
+    /// x^(q^K) = x mod f and gcd(x^(q^(K/r)) - x, f) = 1 for every prime r | K
+    pub fn is_irreducible(modulus: [F; K]) -> bool {
+        let one = modulus[0].one();
+        let mut x_coefficients = [one.zero(); K];
+        let x = if K == 1 {
+            // x = -m_0 mod (x + m_0)
+            x_coefficients[0] = one.zero() - modulus[0];
+            Self::new(x_coefficients, modulus)
+        } else {
+            x_coefficients[1] = one;
+            Self::new(x_coefficients, modulus)
+        };
+
+        // x^(q^j) mod f
+        let x_pow = |j: usize| (0..j).fold(x, |acc, _| acc.frobenius());
+
+        if x_pow(K) != x {
+            return false;
+        }
+
+        let mut f = modulus.to_vec();
+        f.push(one);
+
+        prime_factors(K).into_iter().all(|r| {
+            let h = (x_pow(K / r) - x).coefficients.to_vec();
+            let g = poly_gcd(f.clone(), h);
+            g.len() == 1
+        })
+    }
+}
+
+impl<F: BaseField, const K: usize> FieldTrait for ExtensionField<F, K> {
+    type Modulus = [F; K];
+
+    fn modulus(&self) -> [F; K] {
+        self.modulus
+    }
+
+    /// Extended Euclid over F[x]: s * a + t * f = 1 => a^(-1) = s mod f
+    fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+
+        let one = self.coefficients[0].one();
+        let mut f = self.modulus.to_vec();
+        f.push(one);
+
+        let (mut old_r, mut r) = (trim(self.coefficients.to_vec()), f);
+        let (mut old_s, mut s) = (vec![one], vec![]);
+
+        while !r.is_empty() {
+            let (quotient, remainder) = poly_div_rem(&old_r, &r);
+            (old_r, r) = (r, remainder);
+            let new_s = poly_sub(&old_s, &poly_mul(&quotient, &s));
+            (old_s, s) = (s, new_s);
+        }
+
+        // gcd must be a non-zero constant
+        if old_r.len() != 1 {
+            return None;
+        }
+
+        let scale = old_r[0].inverse()?;
+        let mut coefficients = [one.zero(); K];
+        for (coefficient, s_i) in coefficients.iter_mut().zip(old_s.iter()) {
+            *coefficient = *s_i * scale;
+        }
+
+        Some(Self::new(coefficients, self.modulus))
+    }
+
+    fn pow(&self, exponent: usize) -> Self {
+        self.pow_limbs(&[exponent as u64])
+    }
+
+    /// Tonelli-Shanks over the multiplicative group of order q^K - 1
+    fn sqrt(&self) -> Option<Self> {
+        if self.is_zero() {
+            return Some(*self);
+        }
+
+        let order = self.group_order();
+
+        // characteristic 2: squaring is a bijection, sqrt(a) = a^(q^K / 2)
+        if self.coefficients[0].order().is_multiple_of(2) {
+            // q^K - 1 is odd, so q^K / 2 = (q^K - 1) / 2 + 1
+            let mut half_field_order = order.clone();
+            shift_right_one(&mut half_field_order);
+            add_one(&mut half_field_order);
+            return Some(self.pow_limbs(&half_field_order));
+        }
+
+        if self.legendre_symbol() != 1 {
+            return None;
+        }
+
+        // half of the field are non-residues, but a reducible modulus may leave none
+        let field_size = self.coefficients[0]
+            .order()
+            .checked_pow(K as u32)
+            .unwrap_or(u64::MAX);
+        let z = (2..field_size)
+            .map(|index| self.element_from_index(index))
+            .find(|candidate| candidate.legendre_symbol() == -1)?;
+
+        tonelli_shanks(*self, z, self.one(), &order)
+    }
+
+    /// Euler's criterion in F_q^K, a^((q^K - 1) / 2)
+    fn legendre_symbol(&self) -> i8 {
+        if self.is_zero() {
+            return 0;
+        }
+        if self.coefficients[0].order().is_multiple_of(2) {
+            return 1;
+        }
+
+        let mut half = self.group_order();
+        shift_right_one(&mut half);
+        if self.pow_limbs(&half) == self.one() {
+            1
+        } else {
+            -1
+        }
+    }
+
+    fn zero(&self) -> Self {
+        Self::from_base(self.coefficients[0].zero(), self.modulus)
+    }
+
+    fn one(&self) -> Self {
+        Self::from_base(self.coefficients[0].one(), self.modulus)
+    }
+}
+
+impl<F: BaseField, const K: usize> Add for ExtensionField<F, K> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        assert!(
+            self.modulus == other.modulus,
+            "Add Operation should be within the same Field"
+        );
+
+        let mut coefficients = self.coefficients;
+        for (a, b) in coefficients.iter_mut().zip(other.coefficients.iter()) {
+            *a = *a + *b;
+        }
+
+        Self::new(coefficients, self.modulus)
+    }
+}
+
+impl<F: BaseField, const K: usize> Sub for ExtensionField<F, K> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        assert!(
+            self.modulus == other.modulus,
+            "Sub Operation should be within the same Field"
+        );
+
+        let mut coefficients = self.coefficients;
+        for (a, b) in coefficients.iter_mut().zip(other.coefficients.iter()) {
+            *a = *a - *b;
+        }
+
+        Self::new(coefficients, self.modulus)
+    }
+}
+
+impl<F: BaseField, const K: usize> Mul for ExtensionField<F, K> {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        assert!(
+            self.modulus == other.modulus,
+            "Mul Operation should be within the same Field"
+        );
+
+        let zero = self.coefficients[0].zero();
+        let mut product = vec![zero; 2 * K - 1];
+        for (i, a) in self.coefficients.iter().enumerate() {
+            for (j, b) in other.coefficients.iter().enumerate() {
+                product[i + j] = product[i + j] + *a * *b;
+            }
+        }
+
+        // x^K = -(m_(K-1) x^(K-1) + ... + m_0), folding from the top degree down
+        for degree in (K..2 * K - 1).rev() {
+            let top = product[degree];
+            for (i, m_i) in self.modulus.iter().enumerate() {
+                product[degree - K + i] = product[degree - K + i] - top * *m_i;
+            }
+        }
+
+        let mut coefficients = [zero; K];
+        coefficients.copy_from_slice(&product[..K]);
+
+        Self::new(coefficients, self.modulus)
+    }
+}
+
+impl<F: BaseField, const K: usize> Div for ExtensionField<F, K> {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        assert!(
+            self.modulus == other.modulus,
+            "Div Operation should be within the same Field"
+        );
+        assert!(!other.is_zero(), "Division by zero");
+
+        let inverse = other.inverse().expect("No multiplicative inverse exists");
+        self * inverse
+    }
+}
+
+impl<F: BaseField, const K: usize> PartialEq for ExtensionField<F, K> {
+    fn eq(&self, other: &Self) -> bool {
+        assert!(
+            self.modulus == other.modulus,
+            "You can only compare between same Field"
+        );
+        self.coefficients == other.coefficients
+    }
+}
+
+// drops leading zero coefficients, the zero polynomial is the empty vector
+fn trim<F: BaseField>(mut poly: Vec<F>) -> Vec<F> {
+    while let Some(last) = poly.last() {
+        if *last != last.zero() {
+            break;
+        }
+        poly.pop();
+    }
+    poly
+}
+
+fn poly_sub<F: BaseField>(a: &[F], b: &[F]) -> Vec<F> {
+    let length = a.len().max(b.len());
+    let mut result = Vec::with_capacity(length);
+
+    for i in 0..length {
+        result.push(match (a.get(i), b.get(i)) {
+            (Some(x), Some(y)) => *x - *y,
+            (Some(x), None) => *x,
+            (None, Some(y)) => y.zero() - *y,
+            (None, None) => unreachable!(),
+        });
+    }
+
+    trim(result)
+}
+
+fn poly_mul<F: BaseField>(a: &[F], b: &[F]) -> Vec<F> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+
+    let mut result = vec![a[0].zero(); a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            result[i + j] = result[i + j] + *x * *y;
+        }
+    }
+
+    trim(result)
+}
+
+// a = q * b + r with deg r < deg b, b must be non-zero and trimmed
+fn poly_div_rem<F: BaseField>(a: &[F], b: &[F]) -> (Vec<F>, Vec<F>) {
+    let mut remainder = trim(a.to_vec());
+    if remainder.len() < b.len() {
+        return (vec![], remainder);
+    }
+
+    let lead_inverse = b[b.len() - 1]
+        .inverse()
+        .expect("Leading coefficient should be invertible");
+    let mut quotient = vec![b[0].zero(); remainder.len() - b.len() + 1];
+
+    while remainder.len() >= b.len() {
+        let shift = remainder.len() - b.len();
+        let coefficient = remainder[remainder.len() - 1] * lead_inverse;
+        quotient[shift] = coefficient;
+
+        for (i, b_i) in b.iter().enumerate() {
+            remainder[shift + i] = remainder[shift + i] - coefficient * *b_i;
+        }
+        remainder = trim(remainder);
+    }
+
+    (trim(quotient), remainder)
+}
+
+fn poly_gcd<F: BaseField>(a: Vec<F>, b: Vec<F>) -> Vec<F> {
+    let (mut a, mut b) = (trim(a), trim(b));
+    while !b.is_empty() {
+        let (_, remainder) = poly_div_rem(&a, &b);
+        (a, b) = (b, remainder);
+    }
+    a
+}
+
+fn add_one(limbs: &mut Vec<u64>) {
+    for limb in limbs.iter_mut() {
+        let (sum, overflow) = limb.overflowing_add(1);
+        *limb = sum;
+        if !overflow {
+            return;
+        }
+    }
+    limbs.push(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn f(value: usize) -> Field {
+        Field::new(value, 7)
+    }
+
+    // F_49 = F_7[x] / (x^2 + 1), -1 is a non-residue mod 7
+    fn f49(c0: usize, c1: usize) -> ExtensionField<Field, 2> {
+        ExtensionField::new([f(c0), f(c1)], [f(1), f(0)])
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        // (1 + 2x)(3 + 4x) = 3 + 10x + 8x^2 = (3 - 8) + 10x = 2 + 3x mod 7
+        assert_eq!(f49(1, 2) * f49(3, 4), f49(2, 3));
+        assert_eq!(f49(1, 2) + f49(6, 6), f49(0, 1));
+        assert_eq!(f49(1, 2) - f49(3, 4), f49(5, 5));
+        // x^2 = -1
+        assert_eq!(f49(0, 1).pow(2), f49(6, 0));
+        assert_eq!(f49(0, 1).pow(4), f49(1, 0));
+    }
+
+    #[test]
+    fn test_inverse() {
+        for c0 in 0..7 {
+            for c1 in 0..7 {
+                let a = f49(c0, c1);
+                match a.inverse() {
+                    Some(inverse) => assert_eq!(a * inverse, a.one()),
+                    None => assert!(a.is_zero()),
+                }
+            }
+        }
+        assert_eq!(f49(3, 5) / f49(3, 5), f49(1, 0));
+    }
+
+    #[test]
+    fn test_frobenius() {
+        let a = f49(3, 5);
+        let b = f49(2, 6);
+
+        // conjugation: (c0 + c1 x)^7 = c0 - c1 x, since x^7 = -x
+        assert_eq!(a.frobenius(), f49(3, 2));
+        assert_eq!(a.frobenius().frobenius(), a);
+        assert_eq!((a * b).frobenius(), a.frobenius() * b.frobenius());
+        assert_eq!((a + b).frobenius(), a.frobenius() + b.frobenius());
+
+        // norm a * a^7 lies in the base field
+        assert_eq!((a * a.frobenius()).coefficients[1], f(0));
+    }
+
+    #[test]
+    fn test_sqrt() {
+        let mut squares = 0;
+        for c0 in 0..7 {
+            for c1 in 0..7 {
+                let a = f49(c0, c1);
+                if let Some(root) = a.sqrt() {
+                    assert_eq!(root * root, a);
+                    squares += 1;
+                } else {
+                    assert_eq!(a.legendre_symbol(), -1);
+                }
+            }
+        }
+        // zero plus half of the multiplicative group
+        assert_eq!(squares, 1 + 48 / 2);
+
+        // every base field element is a square in the quadratic extension
+        for c0 in 0..7 {
+            assert!(f49(c0, 0).is_square());
+        }
+    }
+
+    #[test]
+    fn test_cubic_extension() {
+        // F_5^3 = F_5[x] / (x^3 + x + 1)
+        let modulus = [Field::new(1, 5), Field::new(1, 5), Field::new(0, 5)];
+        assert!(ExtensionField::is_irreducible(modulus));
+
+        let a = ExtensionField::new(
+            [Field::new(2, 5), Field::new(3, 5), Field::new(4, 5)],
+            modulus,
+        );
+        assert_eq!(a * a.inverse().unwrap(), a.one());
+        // a^(5^3 - 1) = 1
+        assert_eq!(a.pow(124), a.one());
+        assert_eq!(a.frobenius().frobenius().frobenius(), a);
+        let square = a * a;
+        assert_eq!(square.sqrt().unwrap().pow(2), square);
+    }
+
+    #[test]
+    fn test_is_irreducible() {
+        // x^2 + 1 over F_7 is irreducible, x^2 + 1 over F_5 = (x - 2)(x + 2) isn't
+        assert!(ExtensionField::is_irreducible([f(1), f(0)]));
+        assert!(!ExtensionField::is_irreducible([
+            Field::new(1, 5),
+            Field::new(0, 5)
+        ]));
+
+        // x^4 + 3x^2 + 2 = (x^2 + 1)(x^2 + 2) over F_7 has no roots but is reducible
+        assert!(!ExtensionField::is_irreducible([f(2), f(0), f(3), f(0)]));
+
+        // exhaustive count of monic irreducible quadratics over F_5: (25 - 5) / 2 = 10
+        let count = (0..25)
+            .filter(|i| {
+                ExtensionField::is_irreducible([Field::new(i % 5, 5), Field::new(i / 5, 5)])
+            })
+            .count();
+        assert_eq!(count, 10);
+    }
+
+    #[test]
+    fn test_try_new() {
+        let a = ExtensionField::try_new([f(3), f(5)], [f(1), f(0)]).unwrap();
+        assert_eq!(a, f49(3, 5));
+
+        // x^2 + 1 = (x - 2)(x + 2) over F_5
+        let modulus = [Field::new(1, 5), Field::new(0, 5)];
+        assert_eq!(
+            ExtensionField::try_new([Field::new(2, 5), Field::new(0, 5)], modulus),
+            Err(FieldError::ReducibleModulus)
+        );
+    }
+
+    #[test]
+    fn test_over_binary_field() {
+        // GF(4) = GF(2)[y] / (y^2 + y + 1), then GF(16) = GF(4)[x] / (x^2 + x + w)
+        let g = |v: u64| BinaryField::new(v, 0b111);
+        let modulus = [g(0b10), g(1)];
+        assert!(ExtensionField::is_irreducible(modulus));
+
+        let a = ExtensionField::new([g(0b11), g(0b10)], modulus);
+        assert_eq!(a * a.inverse().unwrap(), a.one());
+        assert_eq!(a.pow(15), a.one());
+        assert_eq!(a.sqrt().unwrap().pow(2), a);
+        assert_eq!(a.frobenius().frobenius(), a);
+    }
+
+    #[test]
+    fn test_over_small_prime_field() {
+        type F = SmallPrimeField<101>;
+        // x^2 - 2 is irreducible since 2 is a non-residue mod 101
+        let modulus = [F::from(99), F::from(0)];
+        assert!(ExtensionField::is_irreducible(modulus));
+
+        let a = ExtensionField::new([F::from(17), F::from(42)], modulus);
+        let b = a * a;
+        assert_eq!(b * FieldTrait::inverse(&b).unwrap(), FieldTrait::one(&b));
+        assert_eq!(FieldTrait::pow(&FieldTrait::sqrt(&b).unwrap(), 2), b);
+    }
+}
@@ -1,15 +1,55 @@
-use crate::utils::mod_pow;
+use crate::params::is_prime;
+use crate::utils::{mod_inverse, mod_pow, tonelli_shanks};
 use std::ops::{Add, Div, Mul, Sub};
 
-pub trait FieldTrait {
-    fn modulus(&self) -> usize;
-    fn inverse(&self) -> Option<Field>;
-    fn pow(&self, exponent: usize) -> Field;
-    fn sqrt(&self) -> Option<Field>;
+pub trait FieldTrait: Sized {
+    type Modulus;
+
+    fn modulus(&self) -> Self::Modulus;
+    fn inverse(&self) -> Option<Self>;
+    fn pow(&self, exponent: usize) -> Self;
+    fn sqrt(&self) -> Option<Self>;
+    fn legendre_symbol(&self) -> i8;
+    fn is_square(&self) -> bool {
+        self.legendre_symbol() != -1
+    }
     fn zero(&self) -> Self;
     fn one(&self) -> Self;
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldError {
+    InvalidModulus(usize),
+    CompositeModulus(usize),
+    ModulusMismatch(usize, usize),
+    DivisionByZero,
+    NoInverse(Field),
+    Overflow,
+    ReducibleModulus,
+}
+
+impl std::fmt::Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldError::InvalidModulus(modulus) => {
+                write!(f, "modulus {} should be greater than 1", modulus)
+            }
+            FieldError::CompositeModulus(modulus) => write!(f, "modulus {} is not prime", modulus),
+            FieldError::ModulusMismatch(left, right) => {
+                write!(f, "moduli {} and {} do not match", left, right)
+            }
+            FieldError::DivisionByZero => write!(f, "division by zero"),
+            FieldError::NoInverse(value) => {
+                write!(f, "{} has no inverse modulo {}", value.value, value.modulus)
+            }
+            FieldError::Overflow => write!(f, "arithmetic overflow"),
+            FieldError::ReducibleModulus => write!(f, "extension modulus is not irreducible"),
+        }
+    }
+}
+
+impl std::error::Error for FieldError {}
+
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Field {
     pub value: usize,
@@ -24,28 +64,129 @@ impl Field {
             modulus,
         }
     }
+
+    /// Non-panicking version of `Field::new`
+    pub fn try_new(value: usize, modulus: usize) -> Result<Self, FieldError> {
+        if modulus < 2 {
+            return Err(FieldError::InvalidModulus(modulus));
+        }
+        Ok(Field::new(value, modulus))
+    }
+
+    /// Like `try_new`, but also rejects composite moduli, for which `inverse`,
+    /// `sqrt` and division are not well defined
+    pub fn new_prime(value: usize, modulus: usize) -> Result<Self, FieldError> {
+        if modulus < 2 {
+            return Err(FieldError::InvalidModulus(modulus));
+        }
+        if !is_prime(modulus as u64) {
+            return Err(FieldError::CompositeModulus(modulus));
+        }
+        Ok(Field::new(value, modulus))
+    }
+
+    pub fn checked_add(self, other: Field) -> Result<Field, FieldError> {
+        self.check_same_field(&other)?;
+        self.value
+            .checked_add(other.value)
+            .ok_or(FieldError::Overflow)?;
+        Ok(self + other)
+    }
+
+    pub fn checked_sub(self, other: Field) -> Result<Field, FieldError> {
+        self.check_same_field(&other)?;
+        Ok(self - other)
+    }
+
+    pub fn checked_mul(self, other: Field) -> Result<Field, FieldError> {
+        self.check_same_field(&other)?;
+        self.value
+            .checked_mul(other.value)
+            .ok_or(FieldError::Overflow)?;
+        Ok(self * other)
+    }
+
+    pub fn checked_div(self, other: Field) -> Result<Field, FieldError> {
+        self.check_same_field(&other)?;
+        if other.value == 0 {
+            return Err(FieldError::DivisionByZero);
+        }
+        let inverse = other.inverse().ok_or(FieldError::NoInverse(other))?;
+        self.checked_mul(inverse)
+    }
+
+    pub fn checked_pow(self, exponent: usize) -> Result<Field, FieldError> {
+        match exponent {
+            0 => Ok(self.one()),
+            1 => Ok(self),
+            _ => {
+                // mod_pow squares values up to modulus - 1
+                (self.modulus - 1)
+                    .checked_mul(self.modulus - 1)
+                    .ok_or(FieldError::Overflow)?;
+                Ok(self.pow(exponent))
+            }
+        }
+    }
+
+    fn check_same_field(&self, other: &Field) -> Result<(), FieldError> {
+        if self.modulus != other.modulus {
+            return Err(FieldError::ModulusMismatch(self.modulus, other.modulus));
+        }
+        Ok(())
+    }
+
+    /// Inverts every element with a single field inversion (Montgomery's trick).
+    /// Zero, or any element without an inverse, maps to `None`.
+    pub fn batch_inverse(elements: &[Field]) -> Vec<Option<Field>> {
+        let invertible: Vec<usize> = (0..elements.len())
+            .filter(|&i| elements[i].value != 0)
+            .collect();
+
+        let mut result = vec![None; elements.len()];
+        if invertible.is_empty() {
+            return result;
+        }
+
+        // prefix[k] = a_0 * a_1 * ... * a_(k-1)
+        let one = elements[invertible[0]].one();
+        let mut prefix = Vec::with_capacity(invertible.len());
+        let mut accumulator = one;
+        for &i in invertible.iter() {
+            prefix.push(accumulator);
+            accumulator = accumulator * elements[i];
+        }
+
+        // only fails when the modulus is not prime, fall back to inverting one by one
+        let Some(mut inverse) = accumulator.inverse() else {
+            for &i in invertible.iter() {
+                result[i] = elements[i].inverse();
+            }
+            return result;
+        };
+
+        // walking backwards, inverse = (a_0 * ... * a_k)^(-1)
+        for (k, &i) in invertible.iter().enumerate().rev() {
+            result[i] = Some(inverse * prefix[k]);
+            inverse = inverse * elements[i];
+        }
+
+        result
+    }
 }
 
 impl FieldTrait for Field {
+    type Modulus = usize;
+
     fn modulus(&self) -> usize {
         self.modulus
     }
 
     fn inverse(&self) -> Option<Field> {
-        if self.value == 0 {
-            return None;
-        }
-
-        for i in 1..self.modulus {
-            if (self.value * i) % self.modulus == 1 {
-                return Some(Field::new(i, self.modulus));
-            }
-        }
-        None
+        mod_inverse(self.value, self.modulus).map(|inverse| Field::new(inverse, self.modulus))
     }
 
     fn pow(&self, exponent: usize) -> Field {
-        assert!(exponent > 0, "Exponent must be non-negative");
         if exponent == 0 {
             return Field::new(1, self.modulus);
         }
@@ -53,14 +194,50 @@ impl FieldTrait for Field {
         Field::new(result_value, self.modulus)
     }
 
+    /// Tonelli-Shanks square root, returns `None` for quadratic non-residues
     fn sqrt(&self) -> Option<Self> {
-        if self.value == 0 {
-            return Some(Field::new(0, self.modulus));
+        if self.value == 0 || self.modulus == 2 {
+            return Some(*self);
         }
 
-        let result_value = (self.value as f64).sqrt() as usize;
+        if self.legendre_symbol() != 1 {
+            return None;
+        }
+
+        // p = 3 mod 4 => sqrt(a) = a^((p + 1) / 4)
+        if self.modulus % 4 == 3 {
+            let root = self.pow((self.modulus + 1) / 4);
+            return if root * root == *self {
+                Some(root)
+            } else {
+                None
+            };
+        }
+
+        // any quadratic non-residue z
+        let z = (2..self.modulus)
+            .map(|z| Field::new(z, self.modulus))
+            .find(|z| z.legendre_symbol() == -1)?;
+
+        tonelli_shanks(*self, z, self.one(), &[(self.modulus - 1) as u64])
+    }
+
+    /// Euler's criterion: a^((p - 1) / 2) is 1 for squares, -1 for non-squares
+    /// and 0 for zero
+    fn legendre_symbol(&self) -> i8 {
+        if self.value == 0 {
+            return 0;
+        }
+        if self.modulus == 2 {
+            return 1;
+        }
 
-        Some(Field::new(result_value, self.modulus))
+        let symbol = self.pow((self.modulus - 1) / 2);
+        if symbol == self.one() {
+            1
+        } else {
+            -1
+        }
     }
 
     fn zero(&self) -> Self {
@@ -153,17 +330,140 @@ mod tests {
 
     #[test]
     fn test_sqrt_and_pow() {
-        // square root
-        let field_1 = Field::new(28, 6);
-        let sqrt_result = field_1.sqrt();
-        let expected_sqrt_result = Some(Field::new(2, 6));
-        assert_eq!(sqrt_result, expected_sqrt_result);
+        // square root, p = 3 mod 4
+        let field_1 = Field::new(28, 19);
+        let sqrt_result = field_1.sqrt().unwrap();
+        assert_eq!(sqrt_result * sqrt_result, field_1);
+
+        // square root, p = 1 mod 4
+        let field_1 = Field::new(13, 17);
+        let sqrt_result = field_1.sqrt().unwrap();
+        assert_eq!(sqrt_result * sqrt_result, field_1);
 
         // raise to pow
         let field_2 = Field::new(2, 9);
         let pow_result = field_2.pow(4);
         let expected_pow_result = Field::new(7, 9);
         assert_eq!(pow_result, expected_pow_result);
+        assert_eq!(field_2.pow(0), field_2.one());
+        assert_eq!(field_2.zero().pow(0), field_2.one());
+    }
+
+    #[test]
+    fn test_checked_operations() {
+        let a = Field::new(15, 17);
+        let b = Field::new(5, 17);
+        let c = Field::new(3, 10);
+
+        assert_eq!(a.checked_add(b), Ok(Field::new(3, 17)));
+        assert_eq!(a.checked_sub(b), Ok(Field::new(10, 17)));
+        assert_eq!(a.checked_mul(b), Ok(Field::new(7, 17)));
+        assert_eq!(a.checked_div(b), Ok(Field::new(3, 17)));
+        assert_eq!(a.checked_pow(0), Ok(a.one()));
+        assert_eq!(a.checked_pow(2), Ok(Field::new(4, 17)));
+
+        assert_eq!(a.checked_add(c), Err(FieldError::ModulusMismatch(17, 10)));
+        assert_eq!(a.checked_sub(c), Err(FieldError::ModulusMismatch(17, 10)));
+        assert_eq!(a.checked_mul(c), Err(FieldError::ModulusMismatch(17, 10)));
+        assert_eq!(a.checked_div(c), Err(FieldError::ModulusMismatch(17, 10)));
+        assert_eq!(a.checked_div(a.zero()), Err(FieldError::DivisionByZero));
+        assert_eq!(
+            c.checked_div(Field::new(4, 10)),
+            Err(FieldError::NoInverse(Field::new(4, 10)))
+        );
+
+        assert_eq!(Field::try_new(3, 1), Err(FieldError::InvalidModulus(1)));
+        assert_eq!(Field::try_new(3, 2), Ok(Field::new(1, 2)));
+
+        assert_eq!(Field::new_prime(20, 17), Ok(Field::new(3, 17)));
+        assert_eq!(
+            Field::new_prime(3, 15),
+            Err(FieldError::CompositeModulus(15))
+        );
+        assert_eq!(Field::new_prime(3, 0), Err(FieldError::InvalidModulus(0)));
+        assert_eq!(
+            FieldError::CompositeModulus(15).to_string(),
+            "modulus 15 is not prime"
+        );
+    }
+
+    #[test]
+    fn test_checked_overflow() {
+        let modulus = usize::MAX - 1;
+        let a = Field::new(usize::MAX - 2, modulus);
+
+        assert_eq!(a.checked_mul(a), Err(FieldError::Overflow));
+        assert_eq!(a.checked_add(a), Err(FieldError::Overflow));
+        assert_eq!(a.checked_pow(2), Err(FieldError::Overflow));
+        assert_eq!(a.checked_pow(1), Ok(a));
+        assert_eq!(a.checked_pow(0), Ok(a.one()));
+        assert_eq!(a.checked_sub(a), Ok(a.zero()));
+    }
+
+    #[test]
+    fn test_sqrt_all_residues() {
+        for modulus in [2, 3, 13, 17, 41, 97, 113, 257] {
+            let mut squares = vec![false; modulus];
+            for x in 0..modulus {
+                squares[(x * x) % modulus] = true;
+            }
+
+            for (value, is_square) in squares.into_iter().enumerate() {
+                let field = Field::new(value, modulus);
+                match field.sqrt() {
+                    Some(root) => {
+                        assert!(is_square);
+                        assert_eq!(root * root, field);
+                    }
+                    None => assert!(!is_square),
+                }
+                assert_eq!(field.is_square(), is_square);
+            }
+        }
+    }
+
+    #[test]
+    fn test_legendre_symbol() {
+        // squares mod 11: 1, 3, 4, 5, 9
+        let symbols: Vec<i8> = (0..11)
+            .map(|v| Field::new(v, 11).legendre_symbol())
+            .collect();
+        assert_eq!(symbols, vec![0, 1, -1, 1, 1, 1, -1, -1, -1, 1, -1]);
+        assert!(Field::new(3, 11).is_square());
+        assert!(!Field::new(2, 11).is_square());
+        assert_eq!(Field::new(2, 11).sqrt(), None);
+    }
+
+    #[test]
+    fn test_inverse() {
+        for value in 1..101 {
+            let field = Field::new(value, 101);
+            assert_eq!(field * field.inverse().unwrap(), field.one());
+        }
+        assert_eq!(Field::new(0, 101).inverse(), None);
+
+        // non-invertible in a composite modulus
+        assert_eq!(Field::new(4, 10).inverse(), None);
+        assert_eq!(Field::new(3, 10).inverse(), Some(Field::new(7, 10)));
+    }
+
+    #[test]
+    fn test_batch_inverse() {
+        let elements: Vec<Field> = [5, 0, 17, 96, 1, 0, 42]
+            .iter()
+            .map(|v| Field::new(*v, 97))
+            .collect();
+        let expected: Vec<Option<Field>> = elements.iter().map(|e| e.inverse()).collect();
+
+        assert_eq!(Field::batch_inverse(&elements), expected);
+        assert_eq!(Field::batch_inverse(&[]), vec![]);
+        assert_eq!(Field::batch_inverse(&[Field::new(0, 97)]), vec![None]);
+
+        let composite: Vec<Field> = [3, 4, 7].iter().map(|v| Field::new(*v, 10)).collect();
+        assert_eq!(
+            Field::batch_inverse(&composite),
+            vec![Some(Field::new(7, 10)), None, Some(Field::new(3, 10))]
+        );
     }
 
     #[test]
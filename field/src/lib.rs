@@ -1,2 +1,7 @@
+pub mod big_field;
+pub mod binary_field;
+pub mod extension_field;
 pub mod field;
+pub mod params;
+pub mod small_prime_field;
 pub mod utils;
@@ -0,0 +1,209 @@
+//! Parameter discovery for prime fields with a 64-bit modulus: primality testing,
+//! multiplicative generators and roots of unity. Everything here is a `const fn`
+//! so it can also run at compile time (see `small_prime_field`).
+
+pub const fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    ((a as u128 * b as u128) % modulus as u128) as u64
+}
+
+pub const fn pow_mod(base: u64, exponent: u64, modulus: u64) -> u64 {
+    let mut result = 1 % modulus;
+    let mut base = base % modulus;
+    let mut exp = exponent;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        exp >>= 1;
+        base = mul_mod(base, base, modulus);
+    }
+
+    result
+}
+
+/// Deterministic Miller-Rabin, the first twelve prime bases are enough for every n < 2^64
+pub const fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if n < 2 {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < BASES.len() {
+        if n == BASES[i] {
+            return true;
+        }
+        if n.is_multiple_of(BASES[i]) {
+            return false;
+        }
+        i += 1;
+    }
+
+    // n - 1 = d * 2^s with d odd
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    let mut i = 0;
+    'witness: while i < BASES.len() {
+        let mut x = pow_mod(BASES[i], d, n);
+        i += 1;
+
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+
+        let mut r = 1;
+        while r < s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'witness;
+            }
+            r += 1;
+        }
+
+        return false;
+    }
+
+    true
+}
+
+/// Largest s such that 2^s divides p - 1
+pub const fn two_adicity(p: u64) -> u32 {
+    (p - 1).trailing_zeros()
+}
+
+/// Smallest g whose order is p - 1, i.e. g^((p - 1) / q) != 1 for every prime q | p - 1
+pub const fn multiplicative_generator(p: u64) -> u64 {
+    assert!(is_prime(p), "Modulus should be prime");
+    if p == 2 {
+        return 1;
+    }
+
+    let mut candidate = 2;
+
+    'search: while candidate < p {
+        // trial-divide p - 1, testing each prime factor as it is found
+        let mut rest = p - 1;
+        let mut factor = 2;
+        while factor * factor <= rest {
+            if rest.is_multiple_of(factor) {
+                if pow_mod(candidate, (p - 1) / factor, p) == 1 {
+                    candidate += 1;
+                    continue 'search;
+                }
+                while rest.is_multiple_of(factor) {
+                    rest /= factor;
+                }
+            }
+            factor += 1;
+        }
+        if rest > 1 && pow_mod(candidate, (p - 1) / rest, p) == 1 {
+            candidate += 1;
+            continue 'search;
+        }
+
+        return candidate;
+    }
+
+    panic!("Modulus has no multiplicative generator")
+}
+
+/// A primitive n-th root of unity mod p, which exists iff n divides p - 1
+pub const fn root_of_unity(p: u64, n: u64) -> Option<u64> {
+    if n == 0 || !(p - 1).is_multiple_of(n) {
+        return None;
+    }
+
+    Some(pow_mod(multiplicative_generator(p), (p - 1) / n, p))
+}
+
+/// g^((p - 1) / 2^s), a primitive 2^s-th root of unity where s is the two-adicity of p
+pub const fn two_adic_root_of_unity(p: u64) -> u64 {
+    let odd_part = (p - 1) >> two_adicity(p);
+    pow_mod(multiplicative_generator(p), odd_part, p)
+}
+
+/// Smallest prime p >= `lower_bound` of the form c * 2^s + 1, i.e. one that supports
+/// radix-2 FFTs of every size up to 2^s
+pub const fn fft_friendly_prime(s: u32, lower_bound: u64) -> Option<u64> {
+    let step = 1u64 << s;
+    let mut c = if lower_bound <= 1 {
+        1
+    } else {
+        (lower_bound - 1).div_ceil(step)
+    };
+
+    while c <= (u64::MAX - 1) >> s {
+        let candidate = c * step + 1;
+        if is_prime(candidate) {
+            return Some(candidate);
+        }
+        c += 1;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_prime() {
+        let primes: Vec<u64> = (0..50).filter(|n| is_prime(*n)).collect();
+        assert_eq!(
+            primes,
+            vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47]
+        );
+
+        // Carmichael numbers and strong pseudoprimes to small bases
+        assert!(!is_prime(561));
+        assert!(!is_prime(3215031751));
+        assert!(!is_prime(3825123056546413051));
+
+        assert!(is_prime(2013265921));
+        assert!(is_prime(18446744069414584321));
+        assert!(is_prime(18446744073709551557));
+        assert!(!is_prime(u64::MAX));
+    }
+
+    #[test]
+    fn test_generator_and_two_adicity() {
+        assert_eq!(multiplicative_generator(17), 3);
+        assert_eq!(multiplicative_generator(97), 5);
+        assert_eq!(multiplicative_generator(2013265921), 31);
+        assert_eq!(multiplicative_generator(18446744069414584321), 7);
+
+        assert_eq!(two_adicity(17), 4);
+        assert_eq!(two_adicity(2013265921), 27);
+        assert_eq!(two_adicity(18446744069414584321), 32);
+    }
+
+    #[test]
+    fn test_root_of_unity() {
+        let p = 97;
+        for n in [1, 2, 3, 4, 6, 8, 12, 16, 32, 48, 96] {
+            let omega = root_of_unity(p, n).unwrap();
+            assert_eq!(pow_mod(omega, n, p), 1);
+            // primitive: no smaller power is 1
+            assert!((1..n).all(|k| pow_mod(omega, k, p) != 1));
+        }
+        assert_eq!(root_of_unity(p, 5), None);
+        assert_eq!(root_of_unity(p, 64), None);
+
+        let omega = two_adic_root_of_unity(2013265921);
+        assert_eq!(pow_mod(omega, 1 << 27, 2013265921), 1);
+        assert_ne!(pow_mod(omega, 1 << 26, 2013265921), 1);
+    }
+
+    #[test]
+    fn test_fft_friendly_prime() {
+        assert_eq!(fft_friendly_prime(4, 0), Some(17));
+        assert_eq!(fft_friendly_prime(5, 0), Some(97));
+        assert_eq!(fft_friendly_prime(27, 1 << 30), Some(2013265921));
+        assert_eq!(fft_friendly_prime(32, 1 << 63), Some(9223372195768565761));
+        assert_eq!(fft_friendly_prime(63, 1 << 63), None);
+    }
+}
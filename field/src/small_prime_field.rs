@@ -0,0 +1,141 @@
+use crate::field::FieldTrait;
+use crate::params::{is_prime, multiplicative_generator, two_adic_root_of_unity};
+use ark_ff::{BigInt, Fp64, LegendreSymbol, MontBackend, MontConfig};
+
+/// A prime field with a compile-time modulus `P` (an odd prime below 2^63) that
+/// implements arkworks' `PrimeField` and `FftField`, so it can be plugged into any
+/// of the arkworks-generic crates in the workspace, e.g. `SmallPrimeField<17>`.
+pub type SmallPrimeField<const P: u64> = Fp64<MontBackend<SmallPrimeConfig<P>, 1>>;
+
+/// Montgomery backend configuration for `SmallPrimeField<P>`. The multiplicative
+/// generator and two-adic root of unity are derived from `P` at compile time.
+pub struct SmallPrimeConfig<const P: u64>;
+
+impl<const P: u64> MontConfig<1> for SmallPrimeConfig<P> {
+    const MODULUS: BigInt<1> = {
+        assert!(P > 2 && is_prime(P), "Modulus should be an odd prime");
+        // the single-limb Montgomery backend needs the top bit free
+        assert!(P >> 63 == 0, "Modulus should be below 2^63");
+        BigInt([P])
+    };
+
+    const GENERATOR: SmallPrimeField<P> =
+        SmallPrimeField::new(BigInt([multiplicative_generator(P)]));
+
+    const TWO_ADIC_ROOT_OF_UNITY: SmallPrimeField<P> =
+        SmallPrimeField::new(BigInt([two_adic_root_of_unity(P)]));
+}
+
+impl<const P: u64> FieldTrait for SmallPrimeField<P> {
+    type Modulus = u64;
+
+    fn modulus(&self) -> u64 {
+        P
+    }
+
+    fn inverse(&self) -> Option<Self> {
+        ark_ff::Field::inverse(self)
+    }
+
+    fn pow(&self, exponent: usize) -> Self {
+        ark_ff::Field::pow(self, [exponent as u64])
+    }
+
+    fn sqrt(&self) -> Option<Self> {
+        ark_ff::Field::sqrt(self)
+    }
+
+    fn legendre_symbol(&self) -> i8 {
+        match ark_ff::Field::legendre(self) {
+            LegendreSymbol::Zero => 0,
+            LegendreSymbol::QuadraticResidue => 1,
+            LegendreSymbol::QuadraticNonResidue => -1,
+        }
+    }
+
+    fn zero(&self) -> Self {
+        <Self as ark_ff::Zero>::zero()
+    }
+
+    fn one(&self) -> Self {
+        <Self as ark_ff::One>::one()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SmallPrimeField;
+    use ark_ff::{BigInt, FftField, Field, One, PrimeField, Zero};
+
+    type F17 = SmallPrimeField<17>;
+    type F97 = SmallPrimeField<97>;
+    // 15 * 2^27 + 1
+    type BabyBear = SmallPrimeField<2013265921>;
+    // 2^61 - 1
+    type Mersenne61 = SmallPrimeField<2305843009213693951>;
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(F17::GENERATOR, F17::from(3));
+        assert_eq!(F17::TWO_ADICITY, 4);
+        assert_eq!(F97::GENERATOR, F97::from(5));
+        assert_eq!(F97::TWO_ADICITY, 5);
+        assert_eq!(BabyBear::GENERATOR, BabyBear::from(31));
+        assert_eq!(BabyBear::TWO_ADICITY, 27);
+        assert_eq!(Mersenne61::GENERATOR, Mersenne61::from(37));
+        assert_eq!(Mersenne61::TWO_ADICITY, 1);
+        assert_eq!(F97::MODULUS, BigInt([97]));
+    }
+
+    #[test]
+    fn test_two_adic_root_of_unity() {
+        let omega = F17::TWO_ADIC_ROOT_OF_UNITY;
+        assert_eq!(omega.pow([16]), F17::one());
+        assert_ne!(omega.pow([8]), F17::one());
+
+        let omega = BabyBear::get_root_of_unity(1 << 10).unwrap();
+        assert_eq!(omega.pow([1 << 10]), BabyBear::one());
+        assert_ne!(omega.pow([1 << 9]), BabyBear::one());
+        assert!(BabyBear::get_root_of_unity(1 << 28).is_none());
+    }
+
+    #[test]
+    fn test_exhaustive_arithmetic() {
+        for a in 0..17u64 {
+            for b in 0..17u64 {
+                let (x, y) = (F17::from(a), F17::from(b));
+                assert_eq!(x + y, F17::from((a + b) % 17));
+                assert_eq!(x * y, F17::from((a * b) % 17));
+                assert_eq!(x - y, F17::from((a + 17 - b) % 17));
+                if b != 0 {
+                    assert_eq!((x / y) * y, x);
+                }
+            }
+            assert_eq!(F17::from(a).into_bigint(), BigInt([a]));
+        }
+        assert_eq!(-F17::one(), F17::from(16));
+    }
+
+    #[test]
+    fn test_field_trait() {
+        use crate::field::FieldTrait;
+
+        for value in [2u64, 12345, 1 << 40, 1 << 62, 2305843009213693950] {
+            let a = Mersenne61::from(value);
+            let square = a * a;
+            let root = FieldTrait::sqrt(&square).unwrap();
+            assert!(root == a || root == -a);
+            assert_eq!(FieldTrait::legendre_symbol(&square), 1);
+        }
+
+        let three = F97::from(3);
+        assert_eq!(FieldTrait::modulus(&three), 97);
+        assert_eq!(
+            FieldTrait::inverse(&three).unwrap() * three,
+            <F97 as One>::one()
+        );
+        assert_eq!(FieldTrait::pow(&three, 4), F97::from(81));
+        assert!(FieldTrait::inverse(&<F97 as Zero>::zero()).is_none());
+        assert!(!FieldTrait::is_square(&F97::GENERATOR));
+    }
+}
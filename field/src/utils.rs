@@ -1,3 +1,5 @@
+use std::ops::Mul;
+
 pub fn mod_pow(base: usize, exponent: usize, modulus: usize) -> usize {
     if modulus == 1 {
         return 0;
@@ -18,6 +20,124 @@ pub fn mod_pow(base: usize, exponent: usize, modulus: usize) -> usize {
     result
 }
 
+/// Inverse of `value` modulo `modulus` using the extended Euclidean algorithm,
+/// `None` if gcd(value, modulus) != 1
+pub fn mod_inverse(value: usize, modulus: usize) -> Option<usize> {
+    let (mut old_r, mut r) = ((value % modulus) as i128, modulus as i128);
+    let (mut old_s, mut s) = (1i128, 0i128);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+    }
+
+    if old_r != 1 {
+        return None;
+    }
+
+    Some(old_s.rem_euclid(modulus as i128) as usize)
+}
+
+/// Distinct prime factors of n in increasing order, by trial division
+pub fn prime_factors(n: usize) -> Vec<usize> {
+    let mut factors = vec![];
+    let mut rest = n;
+    let mut factor = 2;
+
+    while factor * factor <= rest {
+        if rest.is_multiple_of(factor) {
+            factors.push(factor);
+            while rest.is_multiple_of(factor) {
+                rest /= factor;
+            }
+        }
+        factor += 1;
+    }
+    if rest > 1 {
+        factors.push(rest);
+    }
+
+    factors
+}
+
+/// Halves a little-endian limb integer in place
+pub fn shift_right_one(limbs: &mut [u64]) {
+    for i in 0..limbs.len() {
+        let next = limbs.get(i + 1).map_or(0, |limb| limb & 1);
+        limbs[i] = (limbs[i] >> 1) | (next << 63);
+    }
+}
+
+/// base^exponent by square and multiply, the exponent given as little-endian limbs
+pub fn pow_limbs<T: Copy + Mul<Output = T>>(base: T, one: T, exponent: &[u64]) -> T {
+    let mut result = one;
+
+    for limb in exponent.iter().rev() {
+        for i in (0..64).rev() {
+            result = result * result;
+            if (limb >> i) & 1 == 1 {
+                result = result * base;
+            }
+        }
+    }
+
+    result
+}
+
+/// Tonelli-Shanks square root of a quadratic residue `a` in a cyclic multiplicative group
+/// of order `group_order` (little-endian limbs), `z` being any quadratic non-residue.
+///
+/// Returns `None` when the group turns out not to be cyclic, which only happens
+/// when the modulus is not prime.
+pub fn tonelli_shanks<T: Copy + PartialEq + Mul<Output = T>>(
+    a: T,
+    z: T,
+    one: T,
+    group_order: &[u64],
+) -> Option<T> {
+    // group_order = q * 2^s with q odd
+    let mut q = group_order.to_vec();
+    let mut s = 0;
+    while q[0] & 1 == 0 {
+        shift_right_one(&mut q);
+        s += 1;
+    }
+    let mut half_q = q.clone();
+    shift_right_one(&mut half_q);
+
+    let mut m = s;
+    let mut c = pow_limbs(z, one, &q);
+    let mut t = pow_limbs(a, one, &q);
+    // r = a^((q + 1) / 2)
+    let mut r = pow_limbs(a, one, &half_q) * a;
+
+    while t != one {
+        // least i with t^(2^i) == 1
+        let mut i = 0;
+        let mut t_2i = t;
+        while t_2i != one {
+            if i == m {
+                return None;
+            }
+            t_2i = t_2i * t_2i;
+            i += 1;
+        }
+
+        let mut b = c;
+        for _ in 0..m - i - 1 {
+            b = b * b;
+        }
+
+        m = i;
+        c = b * b;
+        t = t * c;
+        r = r * b;
+    }
+
+    Some(r)
+}
+
 pub fn check_is_less_than(a: usize, b: usize) -> bool {
     if a < b {
         true
@@ -42,6 +162,62 @@ mod tests {
         assert_eq!(result_3, 2);
     }
 
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 7), Some(5));
+        assert_eq!(mod_inverse(10, 17), Some(12));
+        assert_eq!(mod_inverse(0, 17), None);
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(
+            mod_inverse(2, 18446744073709551557),
+            Some(9223372036854775779)
+        );
+    }
+
+    #[test]
+    fn test_prime_factors() {
+        assert_eq!(prime_factors(1), Vec::<usize>::new());
+        assert_eq!(prime_factors(19), vec![19]);
+        assert_eq!(prime_factors(360), vec![2, 3, 5]);
+        assert_eq!(prime_factors(2 * 65521 * 65521), vec![2, 65521]);
+    }
+
+    #[test]
+    fn test_limb_helpers() {
+        let mut limbs = [1, 3];
+        shift_right_one(&mut limbs);
+        assert_eq!(limbs, [1 << 63, 1]);
+
+        // 3^(2^64 + 1) mod 1009 with a u64 stand-in for a field
+        let exponent = [1, 1];
+        let expected = (0..64).fold(3u64, |acc, _| acc * acc % 1009) * 3 % 1009;
+        assert_eq!(
+            pow_limbs(Mod1009(3), Mod1009(1), &exponent),
+            Mod1009(expected)
+        );
+    }
+
+    #[test]
+    fn test_tonelli_shanks() {
+        // 1009 - 1 = 63 * 2^4, and 11 is a non-residue
+        for x in 1..1009u64 {
+            let square = Mod1009(x * x % 1009);
+            let root = tonelli_shanks(square, Mod1009(11), Mod1009(1), &[1008]).unwrap();
+            assert_eq!(root * root, square);
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Mod1009(u64);
+
+    impl Mul for Mod1009 {
+        type Output = Self;
+
+        fn mul(self, rhs: Self) -> Self {
+            Mod1009(self.0 * rhs.0 % 1009)
+        }
+    }
+
     #[test]
     fn test_check_is_less_than() {
         let result_1 = check_is_less_than(15, 10);
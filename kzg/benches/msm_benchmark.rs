@@ -0,0 +1,47 @@
+use ark_ec::Group;
+use ark_test_curves::bls12_381::{Bls12_381, Fr, G1Projective};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use kzg::{
+    interface::UnivariateKZGInterface, trusted_setup::TrustedSetup, univariate_kzg::UnivariateKZG,
+};
+use msm::{naive_msm, pippenger_msm};
+use polynomial::{DenseUnivariatePolynomial, UnivariatePolynomialTrait};
+
+fn msm_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("msm");
+
+    for log_size in [6, 8, 10] {
+        let size = 1 << log_size;
+        let scalars: Vec<Fr> = (1..=size as u64).map(|i| -Fr::from(i)).collect();
+        let bases: Vec<G1Projective> = scalars
+            .iter()
+            .map(|s| G1Projective::generator() * s)
+            .collect();
+
+        group.bench_with_input(BenchmarkId::new("naive", size), &size, |b, _| {
+            b.iter(|| naive_msm(black_box(&bases), black_box(&scalars)))
+        });
+        group.bench_with_input(BenchmarkId::new("pippenger", size), &size, |b, _| {
+            b.iter(|| pippenger_msm(black_box(&bases), black_box(&scalars)))
+        });
+    }
+
+    group.finish();
+}
+
+fn univariate_commitment_benchmark(c: &mut Criterion) {
+    let degree = 1023;
+    let poly = DenseUnivariatePolynomial::new(
+        (0..=degree as u64)
+            .map(|x| -Fr::from(x))
+            .collect::<Vec<Fr>>(),
+    );
+    let srs: TrustedSetup<Bls12_381> = UnivariateKZG::generate_srs(&Fr::from(10), &degree);
+
+    c.bench_function("univariate_kzg_commitment_1024", |b| {
+        b.iter(|| UnivariateKZG::commitment(black_box(&poly), black_box(&srs)))
+    });
+}
+
+criterion_group!(benches, msm_benchmark, univariate_commitment_benchmark);
+criterion_main!(benches);
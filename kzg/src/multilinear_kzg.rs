@@ -1,8 +1,9 @@
 use ark_ec::{pairing::Pairing, Group};
 use ark_ff::PrimeField;
+use msm::msm;
 use std::marker::PhantomData;
 
-use polynomial::{Multilinear, MultilinearTrait};
+use polynomial::{Multilinear, MultilinearTrait, VariableOrder};
 
 use crate::{
     interface::{MultilinearKZGInterface, TrustedSetupInterface},
@@ -31,7 +32,8 @@ impl<F: PrimeField, P: Pairing> Default for MultilinearKZGProof<F, P> {
 
 impl<F: PrimeField, P: Pairing> MultilinearKZGInterface<F, P> for MultilinearKZG<F, P> {
     fn commitment(poly: &Multilinear<F>, srs: &TrustedSetup<P>) -> P::G1 {
-        let evaluations: Vec<F> = poly.evaluations.clone();
+        // the powers of tau are laid out MSB first
+        let evaluations: Vec<F> = poly.to_order(VariableOrder::MsbFirst).evaluations;
 
         assert_eq!(
             srs.powers_of_tau_in_g1.len(),
@@ -40,11 +42,7 @@ impl<F: PrimeField, P: Pairing> MultilinearKZGInterface<F, P> for MultilinearKZG
             the evaluations of the polynomial should tally!"
         );
 
-        evaluations
-            .iter()
-            .zip(srs.powers_of_tau_in_g1.iter())
-            .map(|(coefficient, power)| power.mul_bigint(coefficient.into_bigint()))
-            .sum()
+        msm(&srs.powers_of_tau_in_g1, &evaluations)
     }
 
     fn open(
@@ -55,7 +53,7 @@ impl<F: PrimeField, P: Pairing> MultilinearKZGInterface<F, P> for MultilinearKZG
         let evaluation = poly_.evaluation(evaluation_points);
 
         let mut proofs = vec![];
-        let mut poly = poly_.clone();
+        let mut poly = poly_.to_order(VariableOrder::MsbFirst);
         let mut final_round_remainder = F::zero();
 
         for (variable_index, eval_point) in evaluation_points.iter().enumerate() {
@@ -117,7 +115,7 @@ impl<F: PrimeField, P: Pairing> MultilinearKZGInterface<F, P> for MultilinearKZG
 mod tests {
     use ark_test_curves::bls12_381::{Bls12_381, Fr as Fr_old};
     use field_tracker::Ft;
-    use polynomial::Multilinear;
+    use polynomial::{Multilinear, VariableOrder};
 
     use super::MultilinearKZG;
     use crate::{
@@ -196,4 +194,35 @@ mod tests {
         assert_eq!(tampered_tau_verify_status, false);
         // println!("{}", Fr::summary());
     }
+
+    #[test]
+    fn test_kzg_under_both_variable_orders() {
+        let prover_points = vec![Fr::from(12), Fr::from(9), Fr::from(28)];
+        let verifier_points = vec![Fr::from(54), Fr::from(90), Fr::from(76)];
+        let tau = TrustedSetup::<Bls12_381>::setup(&prover_points);
+
+        let msb = Multilinear::new((0..8u64).map(|i| Fr::from(3 * i + 1)).collect());
+        let lsb = msb.to_order(VariableOrder::LsbFirst);
+
+        // the commitment is to the polynomial, not to its layout
+        let commit = MultilinearKZG::<Fr, Bls12_381>::commitment(&msb, &tau);
+        assert_eq!(
+            MultilinearKZG::<Fr, Bls12_381>::commitment(&lsb, &tau),
+            commit
+        );
+
+        let proof: MultilinearKZGProof<Fr, Bls12_381> =
+            MultilinearKZG::open(&lsb, &verifier_points, &tau);
+        let msb_proof: MultilinearKZGProof<Fr, Bls12_381> =
+            MultilinearKZG::open(&msb, &verifier_points, &tau);
+
+        assert_eq!(proof.evaluation, msb_proof.evaluation);
+        assert_eq!(proof.proofs, msb_proof.proofs);
+        assert!(MultilinearKZG::verify(
+            &commit,
+            &verifier_points,
+            &proof,
+            &tau
+        ));
+    }
 }
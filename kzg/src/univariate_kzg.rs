@@ -1,6 +1,7 @@
 use crate::{interface::UnivariateKZGInterface, trusted_setup::TrustedSetup};
 use ark_ec::{pairing::Pairing, Group};
 use ark_ff::{Field, PrimeField};
+use msm::msm;
 use polynomial::{DenseUnivariatePolynomial, UnivariatePolynomialTrait};
 use std::marker::PhantomData;
 
@@ -47,14 +48,10 @@ impl<P: Pairing> UnivariateKZGInterface<P> for UnivariateKZG<P> {
         //         the evaluations of the polynomial should tally!"
         // );
 
-        let mut commit = P::G1::default();
-
-        for i in 0..coefficients.len() {
-            let g1_pow_coeff = srs.powers_of_tau_in_g1[i].mul_bigint(coefficients[i].into_bigint());
-            commit += g1_pow_coeff
-        }
-
-        commit
+        msm(
+            &srs.powers_of_tau_in_g1[..coefficients.len()],
+            &coefficients,
+        )
     }
 
     fn open<F: PrimeField>(
@@ -69,13 +66,10 @@ impl<P: Pairing> UnivariateKZGInterface<P> for UnivariateKZG<P> {
         let numerator = poly_ - evaluation_points;
         let quotient = numerator / denominator;
 
-        let mut proof = P::G1::default();
-
-        for i in 0..quotient.coefficients.len() {
-            let g1_pow_coeff =
-                srs.powers_of_tau_in_g1[i].mul_bigint(quotient.coefficients[i].into_bigint());
-            proof += g1_pow_coeff;
-        }
+        let proof = msm(
+            &srs.powers_of_tau_in_g1[..quotient.coefficients.len()],
+            &quotient.coefficients,
+        );
 
         UnivariateKZGProof { evaluation, proof }
     }
@@ -0,0 +1,3 @@
+pub mod pippenger;
+
+pub use pippenger::{msm, naive_msm, pippenger_msm};
@@ -0,0 +1,203 @@
+use ark_ec::CurveGroup;
+use ark_ff::{BigInteger, PrimeField};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+// Below this many points the bucket bookkeeping costs more than it saves
+const NAIVE_THRESHOLD: usize = 8;
+
+/// sum of scalars[i] * bases[i], with Pippenger's bucket method unless the input is tiny
+pub fn msm<G: CurveGroup, F: PrimeField>(bases: &[G], scalars: &[F]) -> G {
+    assert_eq!(
+        bases.len(),
+        scalars.len(),
+        "The number of bases and scalars should be the same"
+    );
+
+    if bases.len() <= NAIVE_THRESHOLD {
+        naive_msm(bases, scalars)
+    } else {
+        pippenger_msm(bases, scalars)
+    }
+}
+
+/// One scalar multiplication per base
+pub fn naive_msm<G: CurveGroup, F: PrimeField>(bases: &[G], scalars: &[F]) -> G {
+    assert_eq!(
+        bases.len(),
+        scalars.len(),
+        "The number of bases and scalars should be the same"
+    );
+
+    bases
+        .iter()
+        .zip(scalars.iter())
+        .map(|(base, scalar)| base.mul_bigint(scalar.into_bigint()))
+        .sum()
+}
+
+/// Pippenger's bucket method.
+///
+/// Every scalar is cut into c-bit digits, s = sum of d_j * 2^(cj). For each window j
+/// the bases are thrown into bucket d_j, and the window sum
+/// sum over d of d * B_d is computed with a running sum from the top bucket down.
+/// The windows are then combined with c doublings each, from the most significant one.
+pub fn pippenger_msm<G: CurveGroup, F: PrimeField>(bases: &[G], scalars: &[F]) -> G {
+    assert_eq!(
+        bases.len(),
+        scalars.len(),
+        "The number of bases and scalars should be the same"
+    );
+
+    let c = window_size(bases.len());
+    let scalars: Vec<F::BigInt> = scalars.iter().map(|scalar| scalar.into_bigint()).collect();
+    let window_starts: Vec<usize> = (0..F::MODULUS_BIT_SIZE as usize).step_by(c).collect();
+
+    #[cfg(feature = "parallel")]
+    let window_sums: Vec<G> = window_starts
+        .par_iter()
+        .map(|start| window_sum(bases, &scalars, *start, c))
+        .collect();
+
+    #[cfg(not(feature = "parallel"))]
+    let window_sums: Vec<G> = window_starts
+        .iter()
+        .map(|start| window_sum(bases, &scalars, *start, c))
+        .collect();
+
+    let mut result = G::zero();
+    for sum in window_sums.iter().rev() {
+        for _ in 0..c {
+            result.double_in_place();
+        }
+        result += sum;
+    }
+
+    result
+}
+
+// sum over d of d * B_d, where B_d is the sum of the bases whose digit at `start` is d
+fn window_sum<G: CurveGroup, B: BigInteger>(
+    bases: &[G],
+    scalars: &[B],
+    start: usize,
+    c: usize,
+) -> G {
+    let mut buckets = vec![G::zero(); (1 << c) - 1];
+
+    for (base, scalar) in bases.iter().zip(scalars.iter()) {
+        let digit = window_digit(scalar.as_ref(), start, c);
+        if digit != 0 {
+            buckets[digit - 1] += base;
+        }
+    }
+
+    // B_top + (B_top + B_top-1) + ... = sum over d of d * B_d
+    let mut running_sum = G::zero();
+    let mut sum = G::zero();
+    for bucket in buckets.into_iter().rev() {
+        running_sum += bucket;
+        sum += running_sum;
+    }
+
+    sum
+}
+
+// bits [start, start + c) of a little-endian limb integer
+fn window_digit(limbs: &[u64], start: usize, c: usize) -> usize {
+    let limb = start / 64;
+    let offset = start % 64;
+
+    let mut bits = limbs.get(limb).map_or(0, |l| l >> offset);
+    if offset + c > 64 {
+        bits |= limbs.get(limb + 1).map_or(0, |l| l << (64 - offset));
+    }
+
+    (bits & ((1 << c) - 1)) as usize
+}
+
+// roughly ln(n) bits per window, which balances the n additions into buckets
+// against the 2^c additions of the running sum
+fn window_size(n: usize) -> usize {
+    if n < 32 {
+        3
+    } else {
+        (usize::BITS - n.leading_zeros()) as usize * 69 / 100 + 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ec::Group;
+    use ark_ff::{Field, Zero};
+    use ark_test_curves::bls12_381::{Fr, G1Projective, G2Projective};
+
+    // deterministic full width scalars and points
+    fn setup(n: usize) -> (Vec<G1Projective>, Vec<Fr>) {
+        let scalars: Vec<Fr> = (1..=n as u64)
+            .map(|i| Fr::from(i).inverse().unwrap() * Fr::from(7u64).pow([i]))
+            .collect();
+        let bases = scalars
+            .iter()
+            .map(|s| G1Projective::generator() * s.square())
+            .collect();
+
+        (bases, scalars)
+    }
+
+    #[test]
+    fn test_window_digit() {
+        let limbs = [0xf000_0000_0000_0001, 0b1011];
+        assert_eq!(window_digit(&limbs, 0, 4), 1);
+        assert_eq!(window_digit(&limbs, 60, 4), 0xf);
+        // straddles the two limbs: 0b11 from the first, 0b011 from the second
+        assert_eq!(window_digit(&limbs, 62, 5), 0b01111);
+        assert_eq!(window_digit(&limbs, 64, 4), 0b1011);
+        assert_eq!(window_digit(&limbs, 128, 4), 0);
+    }
+
+    #[test]
+    fn test_pippenger_matches_naive() {
+        for n in [1, 2, 7, 8, 9, 31, 32, 100, 257] {
+            let (bases, scalars) = setup(n);
+            let expected = naive_msm(&bases, &scalars);
+
+            assert_eq!(pippenger_msm(&bases, &scalars), expected);
+            assert_eq!(msm(&bases, &scalars), expected);
+        }
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        let g = G1Projective::generator();
+
+        assert_eq!(msm::<G1Projective, Fr>(&[], &[]), G1Projective::zero());
+
+        // zero scalars, the identity and -1 = p - 1, the widest scalar there is
+        let bases = vec![g; 12];
+        let mut scalars = vec![Fr::from(0u64); 12];
+        scalars[3] = -Fr::from(1u64);
+        assert_eq!(pippenger_msm(&bases, &scalars), -g);
+
+        let mut bases = vec![G1Projective::zero(); 12];
+        bases[0] = g;
+        let scalars = vec![Fr::from(5u64); 12];
+        assert_eq!(pippenger_msm(&bases, &scalars), g * Fr::from(5u64));
+
+        // works for G2 as well
+        let bases = vec![G2Projective::generator(); 20];
+        let scalars: Vec<Fr> = (0..20u64).map(Fr::from).collect();
+        assert_eq!(
+            pippenger_msm(&bases, &scalars),
+            G2Projective::generator() * Fr::from(190u64)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The number of bases and scalars should be the same")]
+    fn test_length_mismatch() {
+        let (bases, scalars) = setup(10);
+        let _ = msm(&bases, &scalars[..9]);
+    }
+}
@@ -105,3 +105,26 @@ pub fn create_monomial<F: PrimeField>(
     coeffs[0] = constant;
     DenseUnivariatePolynomial::from_coefficients_vec(coeffs)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_test_curves::bls12_381::Fr;
+    use polynomial::univariate::domain::Domain;
+
+    #[test]
+    fn test_zh_poly_factors_over_the_domain() {
+        // Z_H(x) = x^n - 1 = prod of (x - ω^i), each root once
+        let group_order = 8;
+        let zh_poly = DenseUnivariatePolynomial::new(zh_values::<Fr>(group_order));
+
+        let mut roots = Domain::<Fr>::new(group_order).get_roots_of_unity();
+        roots.sort();
+        assert_eq!(zh_poly.roots(), Some(roots));
+
+        assert_eq!(
+            zh_poly.square_free_factorization(),
+            vec![(zh_poly.clone(), 1)]
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use ark_test_curves::bls12_381::Fr;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use polynomial::{utils::generate_random_numbers, Multilinear, MultilinearTrait};
+
+fn multilinear_evaluation_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("multilinear_evaluation");
+
+    for n_vars in [10, 14, 18] {
+        let poly = Multilinear::new(generate_random_numbers::<Fr>(1 << n_vars));
+        let point: Vec<Fr> = generate_random_numbers(n_vars);
+
+        // a new polynomial per fixed variable
+        group.bench_with_input(
+            BenchmarkId::new("partial_evaluation", n_vars),
+            &n_vars,
+            |b, _| {
+                b.iter(|| {
+                    let mut result = poly.clone();
+                    for r in point.iter() {
+                        result = result.partial_evaluation(black_box(r), &0);
+                    }
+                    result.evaluations[0]
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("fix_variables", n_vars),
+            &n_vars,
+            |b, _| {
+                b.iter(|| {
+                    let mut result = poly.clone();
+                    result.fix_variables(black_box(&point));
+                    result.evaluations[0]
+                })
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("eq_table", n_vars), &n_vars, |b, _| {
+            b.iter(|| poly.evaluate_with_eq_table(black_box(&point)))
+        });
+    }
+
+    group.finish();
+}
+
+fn eq_poly_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("eq_poly");
+
+    for n_vars in [10, 14, 18] {
+        let point: Vec<Fr> = generate_random_numbers(n_vars);
+        group.bench_with_input(BenchmarkId::new("eq_poly", n_vars), &n_vars, |b, _| {
+            b.iter(|| Multilinear::eq_poly(black_box(&point)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, multilinear_evaluation_benchmark, eq_poly_benchmark);
+criterion_main!(benches);
@@ -9,14 +9,30 @@ pub struct ComposedMultilinear<F: PrimeField> {
     polys: Vec<Multilinear<F>>,
 }
 
+/// Lays every polynomial out like the first one, as the element wise operations need the
+/// same layout in every polynomial
+pub(crate) fn match_first_order<F: PrimeField>(polys: &mut [Multilinear<F>]) {
+    if let Some((first, rest)) = polys.split_first_mut() {
+        for poly in rest {
+            poly.set_order(first.order);
+        }
+    }
+}
+
 impl<F: PrimeField> ComposedMultilinear<F> {
-    pub fn new(polys: Vec<Multilinear<F>>) -> Self {
+    pub fn new(mut polys: Vec<Multilinear<F>>) -> Self {
         let n_vars = polys[0].n_vars;
         assert!(polys.iter().all(|p| p.n_vars == n_vars));
 
+        match_first_order(&mut polys);
+
         ComposedMultilinear { polys }
     }
 
+    pub fn polys(&self) -> &[Multilinear<F>] {
+        &self.polys
+    }
+
     pub fn n_vars(&self) -> usize {
         self.polys[0].n_vars
     }
@@ -1 +1,2 @@
 pub mod composed_multilinear;
+pub mod virtual_polynomial;
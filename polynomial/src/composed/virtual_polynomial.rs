@@ -0,0 +1,345 @@
+use crate::{
+    composed::composed_multilinear::match_first_order, interface::MultilinearTrait,
+    ComposedMultilinear, Multilinear,
+};
+use ark_ff::{BigInteger, PrimeField};
+
+/// coefficient * prod of mles[i] over the indices
+#[derive(Debug, Clone, PartialEq)]
+pub struct VirtualTerm<F: PrimeField> {
+    pub coefficient: F,
+    pub indices: Vec<usize>,
+}
+
+/// A sum of products of multilinear polynomials, sum of c_j * prod over i in S_j of f_i.
+///
+/// The f_i are kept once in a pool that the terms point into, so a polynomial shared by
+/// several products, like W_(i+1) in a GKR layer, is stored and evaluated only once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VirtualPolynomial<F: PrimeField> {
+    pub n_vars: usize,
+    mles: Vec<Multilinear<F>>,
+    terms: Vec<VirtualTerm<F>>,
+}
+
+impl<F: PrimeField> VirtualPolynomial<F> {
+    pub fn new(n_vars: usize) -> Self {
+        VirtualPolynomial {
+            n_vars,
+            mles: vec![],
+            terms: vec![],
+        }
+    }
+
+    pub fn mles(&self) -> &[Multilinear<F>] {
+        &self.mles
+    }
+
+    pub fn terms(&self) -> &[VirtualTerm<F>] {
+        &self.terms
+    }
+
+    /// The index of the polynomial in the pool, adding it if it is not there yet
+    pub fn add_mle(&mut self, mle: Multilinear<F>) -> usize {
+        assert_eq!(
+            mle.n_vars, self.n_vars,
+            "The number of variables in the polynomials must be the same"
+        );
+
+        self.mles.push(mle);
+        match_first_order(&mut self.mles);
+
+        let (mle, pool) = self.mles.split_last().unwrap();
+        match pool.iter().position(|existing| existing == mle) {
+            Some(index) => {
+                self.mles.pop();
+                index
+            }
+            None => self.mles.len() - 1,
+        }
+    }
+
+    /// Adds coefficient * prod of the pooled polynomials at the indices
+    pub fn add_term(&mut self, coefficient: F, indices: Vec<usize>) {
+        assert!(
+            indices.iter().all(|index| *index < self.mles.len()),
+            "The indices should point into the pool of polynomials"
+        );
+
+        self.terms.push(VirtualTerm {
+            coefficient,
+            indices,
+        });
+    }
+
+    /// The degree in each variable, the longest product
+    pub fn max_degree(&self) -> usize {
+        self.terms
+            .iter()
+            .map(|term| term.indices.len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// The values of the polynomial over the boolean hypercube
+    pub fn element_wise_evaluations(&self) -> Vec<F> {
+        (0..1 << self.n_vars)
+            .map(|i| {
+                self.terms
+                    .iter()
+                    .map(|term| {
+                        term.indices.iter().fold(term.coefficient, |acc, index| {
+                            acc * self.mles[*index].evaluations[i]
+                        })
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+
+    pub fn sum_over_the_boolean_hypercube(&self) -> F {
+        self.element_wise_evaluations().iter().sum()
+    }
+
+    /// n_vars, the pool and then every term, the lengths as 8 big-endian bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        bytes.extend((self.n_vars as u64).to_be_bytes());
+        bytes.extend((self.mles.len() as u64).to_be_bytes());
+        for mle in &self.mles {
+            bytes.extend(mle.to_bytes());
+        }
+
+        bytes.extend((self.terms.len() as u64).to_be_bytes());
+        for term in &self.terms {
+            bytes.extend(term.coefficient.into_bigint().to_bytes_be());
+            bytes.extend((term.indices.len() as u64).to_be_bytes());
+            for index in &term.indices {
+                bytes.extend((*index as u64).to_be_bytes());
+            }
+        }
+
+        bytes
+    }
+
+    /// The sum of the products, as the multi-composed sumcheck reads a list of them.
+    /// A zero composed polynomial has no product, so it adds no term.
+    pub fn from_composed(n_vars: usize, polys: &[ComposedMultilinear<F>]) -> Self {
+        let mut virtual_poly = VirtualPolynomial::new(n_vars);
+        for poly in polys.iter().filter(|poly| !poly.is_zero()) {
+            let indices = poly
+                .polys()
+                .iter()
+                .map(|mle| virtual_poly.add_mle(mle.clone()))
+                .collect();
+            virtual_poly.add_term(F::one(), indices);
+        }
+
+        virtual_poly
+    }
+}
+
+impl<F: PrimeField> From<&ComposedMultilinear<F>> for VirtualPolynomial<F> {
+    fn from(poly: &ComposedMultilinear<F>) -> Self {
+        let n_vars = poly.polys().first().map_or(0, |mle| mle.n_vars);
+        Self::from_composed(n_vars, std::slice::from_ref(poly))
+    }
+}
+
+impl<F: PrimeField> MultilinearTrait<F> for VirtualPolynomial<F> {
+    fn partial_evaluation(&self, eval_point: &F, variable_index: &usize) -> Self {
+        VirtualPolynomial {
+            n_vars: self.n_vars - 1,
+            mles: self
+                .mles
+                .iter()
+                .map(|mle| mle.partial_evaluation(eval_point, variable_index))
+                .collect(),
+            terms: self.terms.clone(),
+        }
+    }
+
+    fn partial_evaluations(&self, points: &[F], variable_indices: &Vec<usize>) -> Self {
+        assert_eq!(
+            points.len(),
+            variable_indices.len(),
+            "The length of evaluation_points and variable_indices should be the same"
+        );
+
+        points
+            .iter()
+            .zip(variable_indices.iter())
+            .fold(self.clone(), |poly, (point, index)| {
+                poly.partial_evaluation(point, index)
+            })
+    }
+
+    /// Every pooled polynomial is evaluated once, however many terms use it
+    fn evaluation(&self, evaluation_points: &[F]) -> F {
+        let evaluations: Vec<F> = self
+            .mles
+            .iter()
+            .map(|mle| mle.evaluation(evaluation_points))
+            .collect();
+
+        self.terms
+            .iter()
+            .map(|term| {
+                term.indices
+                    .iter()
+                    .fold(term.coefficient, |acc, index| acc * evaluations[*index])
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{interface::ComposedMultilinearTrait, VariableOrder};
+    use ark_test_curves::bls12_381::Fr;
+
+    fn mle(values: &[u64]) -> Multilinear<Fr> {
+        Multilinear::new(values.iter().map(|v| Fr::from(*v)).collect())
+    }
+
+    // add(x) * (w(x) + v(x)) + mul(x) * w(x) * v(x) - 3 w(x)
+    fn gate() -> VirtualPolynomial<Fr> {
+        let mut poly = VirtualPolynomial::new(2);
+        let add = poly.add_mle(mle(&[1, 0, 0, 1]));
+        let mul = poly.add_mle(mle(&[0, 1, 1, 0]));
+        let w = poly.add_mle(mle(&[3, 5, 7, 11]));
+        let v = poly.add_mle(mle(&[2, 4, 6, 8]));
+
+        poly.add_term(Fr::from(1), vec![add, w]);
+        poly.add_term(Fr::from(1), vec![add, v]);
+        poly.add_term(Fr::from(1), vec![mul, w, v]);
+        poly.add_term(-Fr::from(3), vec![w]);
+
+        poly
+    }
+
+    #[test]
+    fn test_pool_and_degree() {
+        let mut poly = gate();
+
+        // every polynomial is stored once
+        assert_eq!(poly.mles().len(), 4);
+        assert_eq!(poly.terms().len(), 4);
+        assert_eq!(poly.terms()[2].indices, vec![1, 2, 3]);
+        assert_eq!(poly.max_degree(), 3);
+
+        // adding a polynomial again, in any layout, finds the pooled one
+        let w = poly.mles()[2].clone();
+        assert_eq!(poly.add_mle(w.clone()), 2);
+        assert_eq!(poly.add_mle(w.to_order(VariableOrder::LsbFirst)), 2);
+        assert_eq!(poly.mles().len(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "The indices should point into the pool of polynomials")]
+    fn test_add_term_out_of_pool() {
+        let mut poly = gate();
+        poly.add_term(Fr::from(1), vec![4]);
+    }
+
+    #[test]
+    fn test_evaluation() {
+        let poly = gate();
+        let point = [Fr::from(5), Fr::from(7)];
+
+        let values: Vec<Fr> = poly
+            .mles()
+            .iter()
+            .map(|mle| mle.evaluation(&point))
+            .collect();
+        let (add, mul, w, v) = (values[0], values[1], values[2], values[3]);
+        let expected = add * (w + v) + mul * w * v - Fr::from(3) * w;
+        assert_eq!(poly.evaluation(&point), expected);
+
+        let partial = poly.partial_evaluation(&point[0], &0);
+        assert_eq!(partial.n_vars, 1);
+        assert_eq!(partial.evaluation(&point[1..]), expected);
+
+        // add selects 00 and 11, mul 01 and 10
+        assert_eq!(
+            poly.element_wise_evaluations(),
+            vec![
+                Fr::from(5 - 9),
+                Fr::from(20 - 15),
+                Fr::from(42 - 21),
+                Fr::from(19 - 33)
+            ]
+        );
+        assert_eq!(poly.sum_over_the_boolean_hypercube(), Fr::from(8));
+    }
+
+    #[test]
+    fn test_from_composed() {
+        let (f, g) = (mle(&[0, 1, 2, 3]), mle(&[0, 0, 0, 1]));
+        let composed = ComposedMultilinear::new(vec![f.clone(), g.clone()]);
+        let poly = VirtualPolynomial::from(&composed);
+
+        assert_eq!(poly.max_degree(), composed.max_degree());
+        assert_eq!(
+            poly.element_wise_evaluations(),
+            composed.element_wise_product()
+        );
+
+        // f * g + f^2 keeps a single f
+        let squared = ComposedMultilinear::new(vec![f.clone(), f.clone()]);
+        let poly = VirtualPolynomial::from_composed(2, &[composed.clone(), squared.clone()]);
+        assert_eq!(poly.mles().len(), 2);
+        assert_eq!(poly.terms()[1].indices, vec![0, 0]);
+
+        let point = [Fr::from(2), Fr::from(3)];
+        assert_eq!(
+            poly.evaluation(&point),
+            composed.evaluation(&point) + squared.evaluation(&point)
+        );
+
+        // zero composed polynomials add nothing
+        let zero = VirtualPolynomial::from_composed(2, &[composed.zero()]);
+        assert!(zero.is_zero());
+        assert_eq!(zero.n_vars, 2);
+        assert!(VirtualPolynomial::<Fr>::from_composed(2, &[]).is_zero());
+        assert!(VirtualPolynomial::from(&composed.zero()).is_zero());
+    }
+
+    #[test]
+    fn test_to_bytes() {
+        let poly = gate();
+        let bytes = poly.to_bytes();
+
+        // 3 lengths, 4 pooled polynomials of 4 evaluations and 4 terms
+        let field_size = Fr::from(0).into_bigint().to_bytes_be().len();
+        let terms_size: usize = poly
+            .terms()
+            .iter()
+            .map(|term| field_size + 8 + 8 * term.indices.len())
+            .sum();
+        assert_eq!(bytes.len(), 3 * 8 + 16 * field_size + terms_size);
+        assert_eq!(bytes, poly.clone().to_bytes());
+
+        // the pool is serialized in MSB first order whatever its layout
+        let mut lsb = VirtualPolynomial::new(2);
+        for mle in poly.mles() {
+            lsb.add_mle(mle.to_order(VariableOrder::LsbFirst));
+        }
+        for term in poly.terms() {
+            lsb.add_term(term.coefficient, term.indices.clone());
+        }
+        assert_eq!(lsb.mles()[0].order, VariableOrder::LsbFirst);
+        assert_eq!(lsb.to_bytes(), bytes);
+
+        // a different coefficient changes the bytes
+        let mut other = poly.clone();
+        other.add_term(Fr::from(1), vec![0]);
+        assert_ne!(other.to_bytes(), bytes);
+    }
+}
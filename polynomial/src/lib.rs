@@ -1,12 +1,17 @@
 pub mod composed;
 pub mod interface;
 pub mod multilinear;
+pub mod multivariate;
 pub mod univariate;
 pub mod utils;
 
-pub use composed::composed_multilinear::ComposedMultilinear;
+pub use composed::{
+    composed_multilinear::ComposedMultilinear,
+    virtual_polynomial::{VirtualPolynomial, VirtualTerm},
+};
 pub use interface::{ComposedMultilinearTrait, MultilinearTrait, UnivariatePolynomialTrait};
-pub use multilinear::evaluation_form::Multilinear;
+pub use multilinear::evaluation_form::{Multilinear, VariableOrder};
+pub use multivariate::sparse_multivariate::{MultivariateMonomial, SparseMultivariatePolynomial};
 pub use univariate::{
     dense_univariate::DenseUnivariatePolynomial,
     sparse_univariate::{SparseUnivariatePolynomial, UnivariateMonomial},
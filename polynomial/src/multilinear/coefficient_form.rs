@@ -1,4 +1,7 @@
-use crate::utils::pick_pairs_with_index;
+use crate::{
+    utils::{mobius_transform, pick_pairs_with_index, zeta_transform},
+    Multilinear, VariableOrder,
+};
 use ark_ff::PrimeField;
 use std::{
     fmt::{Display, Formatter, Result},
@@ -31,6 +34,64 @@ impl<F: PrimeField> MultiLinearCoefficientPolynomial<F> {
         MultiLinearCoefficientPolynomial { terms }
     }
 
+    /// number of variables, the longest monomial
+    pub fn n_vars(&self) -> usize {
+        self.terms
+            .iter()
+            .map(|term| term.vars.len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// evaluations over the boolean hypercube with the zeta transform, in O(n 2^n).
+    /// The first variable is the most significant bit of the index, as in `Multilinear`.
+    pub fn to_evaluation_form(&self) -> Multilinear<F> {
+        let n_vars = self.n_vars();
+        let mut values = vec![F::zero(); 1 << n_vars];
+
+        for term in &self.terms {
+            let mask = term
+                .vars
+                .iter()
+                .fold(0, |mask, var| (mask << 1) | *var as usize);
+            // shorter monomials leave the trailing variables out
+            values[mask << (n_vars - term.vars.len())] += term.coefficient;
+        }
+
+        zeta_transform(&mut values);
+        Multilinear::new(values)
+    }
+
+    /// coefficients from evaluations over the boolean hypercube with the möbius transform,
+    /// in O(n 2^n). Zero coefficients are dropped, but the zero polynomial keeps a
+    /// constant term so the number of variables is not lost.
+    pub fn from_evaluation_form(poly: &Multilinear<F>) -> Self {
+        let n_vars = poly.n_vars;
+        let mut coefficients = poly.to_order(VariableOrder::MsbFirst).evaluations;
+        mobius_transform(&mut coefficients);
+
+        let mut terms: Vec<MultiLinearMonomial<F>> = coefficients
+            .into_iter()
+            .enumerate()
+            .filter(|(_, coefficient)| !coefficient.is_zero())
+            .map(|(mask, coefficient)| MultiLinearMonomial {
+                coefficient,
+                vars: (0..n_vars)
+                    .map(|i| (mask >> (n_vars - 1 - i)) & 1 == 1)
+                    .collect(),
+            })
+            .collect();
+
+        if terms.is_empty() {
+            terms.push(MultiLinearMonomial {
+                coefficient: F::zero(),
+                vars: vec![false; n_vars],
+            });
+        }
+
+        MultiLinearCoefficientPolynomial { terms }
+    }
+
     /// partial evaluation of a polynomial
     pub fn partial_evaluation(&self, eval_points: F) -> Self {
         let mut res: MultiLinearCoefficientPolynomial<F> =
@@ -83,6 +144,30 @@ impl<F: PrimeField> MultiLinearCoefficientPolynomial<F> {
     }
 }
 
+impl<F: PrimeField> From<&Multilinear<F>> for MultiLinearCoefficientPolynomial<F> {
+    fn from(poly: &Multilinear<F>) -> Self {
+        Self::from_evaluation_form(poly)
+    }
+}
+
+impl<F: PrimeField> From<Multilinear<F>> for MultiLinearCoefficientPolynomial<F> {
+    fn from(poly: Multilinear<F>) -> Self {
+        Self::from_evaluation_form(&poly)
+    }
+}
+
+impl<F: PrimeField> From<&MultiLinearCoefficientPolynomial<F>> for Multilinear<F> {
+    fn from(poly: &MultiLinearCoefficientPolynomial<F>) -> Self {
+        poly.to_evaluation_form()
+    }
+}
+
+impl<F: PrimeField> From<MultiLinearCoefficientPolynomial<F>> for Multilinear<F> {
+    fn from(poly: MultiLinearCoefficientPolynomial<F>) -> Self {
+        poly.to_evaluation_form()
+    }
+}
+
 impl<F: PrimeField> Mul for MultiLinearCoefficientPolynomial<F> {
     type Output = Self;
     fn mul(self, _rhs: Self) -> Self {
@@ -126,6 +211,7 @@ mod tests {
     use field_tracker::Ft;
 
     use super::{MultiLinearCoefficientPolynomial, MultiLinearMonomial};
+    use crate::{interface::MultilinearTrait, utils::generate_random_numbers, Multilinear};
     use ark_test_curves::bls12_381::Fr as Fq_old;
 
     type Fq = Ft<4, Fq_old>;
@@ -182,4 +268,45 @@ mod tests {
         assert_eq!(degree, 2);
         // println!("{}", Fq::summary());
     }
+
+    #[test]
+    fn test_to_evaluation_form() {
+        // 3 - a - 2b + 5ab takes 3, 1, 2, 5 on 00, 01, 10, 11
+        let polynomial = init();
+        let expected = Multilinear::new(vec![Fq::from(3), Fq::from(1), Fq::from(2), Fq::from(5)]);
+        assert_eq!(polynomial.to_evaluation_form(), expected);
+
+        let evaluation_form: Multilinear<Fq> = polynomial.into();
+        assert_eq!(
+            evaluation_form.evaluation(&[Fq::from(5), Fq::from(6)]),
+            Fq::from(136)
+        );
+    }
+
+    #[test]
+    fn test_from_evaluation_form() {
+        // the same terms, in hypercube order
+        let polynomial = init();
+        let coefficient_form =
+            MultiLinearCoefficientPolynomial::from(&polynomial.to_evaluation_form());
+        assert_eq!(coefficient_form.terms.len(), 4);
+        assert_eq!(coefficient_form.terms[1].coefficient, Fq::from(-2));
+        assert_eq!(coefficient_form.terms[1].vars, vec![false, true]);
+        assert_eq!(coefficient_form.degree(), 2);
+
+        // round trip on random evaluations, agreeing off the hypercube
+        let evaluations: Vec<Fq_old> = generate_random_numbers(1 << 6);
+        let poly = Multilinear::new(evaluations);
+        let coefficient_form = MultiLinearCoefficientPolynomial::from(&poly);
+        assert_eq!(Multilinear::from(&coefficient_form), poly);
+
+        let point: Vec<Fq_old> = generate_random_numbers(6);
+        assert_eq!(coefficient_form.evaluation(&point), poly.evaluation(&point));
+
+        // the zero polynomial keeps its variables
+        let zero = Multilinear::<Fq_old>::additive_identity(3);
+        let coefficient_form = MultiLinearCoefficientPolynomial::from(&zero);
+        assert_eq!(coefficient_form.n_vars(), 3);
+        assert_eq!(coefficient_form.to_evaluation_form(), zero);
+    }
 }
@@ -1,11 +1,40 @@
-use crate::{interface::MultilinearTrait, utils::pick_pairs_with_random_index};
+use crate::{
+    interface::MultilinearTrait,
+    utils::{bit_reverse_permutation, pick_pairs_with_random_index},
+};
 use ark_ff::{BigInteger, PrimeField};
 use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
 
-#[derive(Debug, Clone, PartialEq)]
+/// How the variables map to the bits of an index into the evaluations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VariableOrder {
+    /// x_0 is the most significant bit, f(x_0, ..., x_(n-1)) = evaluations[x_0 x_1 ... x_(n-1)]
+    #[default]
+    MsbFirst,
+    /// x_0 is the least significant bit, f(x_0, ..., x_(n-1)) = evaluations[x_(n-1) ... x_1 x_0]
+    LsbFirst,
+}
+
+#[derive(Debug, Clone)]
 pub struct Multilinear<F: PrimeField> {
     pub n_vars: usize,
     pub evaluations: Vec<F>,
+    pub order: VariableOrder,
+}
+
+/// Compares the polynomials, so the same polynomial in both layouts is equal
+impl<F: PrimeField> PartialEq for Multilinear<F> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.n_vars != other.n_vars {
+            return false;
+        }
+
+        if self.order == other.order {
+            self.evaluations == other.evaluations
+        } else {
+            self.evaluations == other.to_order(self.order).evaluations
+        }
+    }
 }
 
 impl<F: PrimeField> Multilinear<F> {
@@ -22,39 +51,213 @@ impl<F: PrimeField> Multilinear<F> {
         Self {
             n_vars,
             evaluations,
+            order: VariableOrder::MsbFirst,
         }
     }
 
-    pub fn add_distinct(&self, rhs: &Self) -> Self {
-        let mut new_evaluations = Vec::new();
-        let repeat_sequence = rhs.evaluations.len();
+    pub fn new_with_order(evaluations: Vec<F>, order: VariableOrder) -> Self {
+        Self {
+            order,
+            ..Self::new(evaluations)
+        }
+    }
 
-        for i in 0..self.evaluations.len() {
-            for j in 0..repeat_sequence {
-                new_evaluations.push(self.evaluations[i] + rhs.evaluations[j]);
+    // the bit of the evaluation index holding the variable
+    fn bit_of(&self, variable_index: usize) -> usize {
+        match self.order {
+            VariableOrder::MsbFirst => self.n_vars - 1 - variable_index,
+            VariableOrder::LsbFirst => variable_index,
+        }
+    }
+
+    /// The same polynomial with its evaluations laid out in `order`
+    pub fn to_order(&self, order: VariableOrder) -> Self {
+        self.clone().into_order(order)
+    }
+
+    /// Switching between MSB and LSB first reverses the bits of every index
+    pub fn into_order(mut self, order: VariableOrder) -> Self {
+        self.set_order(order);
+        self
+    }
+
+    /// Lays the evaluations out in `order` in place
+    pub fn set_order(&mut self, order: VariableOrder) {
+        if self.order != order {
+            bit_reverse_permutation(&mut self.evaluations, self.n_vars as u32);
+            self.order = order;
+        }
+    }
+
+    /// g(x_0, ..., x_(n-1)) = f(x_(n-1), ..., x_0), which only reinterprets the evaluations
+    pub fn reverse_variables(&self) -> Self {
+        let order = match self.order {
+            VariableOrder::MsbFirst => VariableOrder::LsbFirst,
+            VariableOrder::LsbFirst => VariableOrder::MsbFirst,
+        };
+
+        Self {
+            order,
+            ..self.clone()
+        }
+    }
+
+    /// Relabels the variables, variable i of the result being variable permutation[i] of self
+    pub fn permute_variables(&self, permutation: &[usize]) -> Self {
+        let mut seen = vec![false; self.n_vars];
+        for &index in permutation {
+            assert!(
+                index < self.n_vars && !seen[index],
+                "The variables should be a permutation of 0..n_vars"
+            );
+            seen[index] = true;
+        }
+        assert_eq!(
+            permutation.len(),
+            self.n_vars,
+            "The variables should be a permutation of 0..n_vars"
+        );
+
+        let evaluations = (0..self.evaluations.len())
+            .map(|index| {
+                let source = permutation
+                    .iter()
+                    .enumerate()
+                    .fold(0, |source, (i, &variable)| {
+                        let bit = (index >> self.bit_of(i)) & 1;
+                        source | (bit << self.bit_of(variable))
+                    });
+                self.evaluations[source]
+            })
+            .collect();
+
+        Self {
+            n_vars: self.n_vars,
+            evaluations,
+            order: self.order,
+        }
+    }
+
+    /// The evaluations f(b) over the boolean hypercube, b read from the bits of the index
+    /// with the first variable as the most significant bit
+    pub fn from_fn(n_vars: usize, f: impl Fn(usize) -> F) -> Self {
+        Self::new((0..1 << n_vars).map(f).collect())
+    }
+
+    /// eq(x, r) = prod of (x_i r_i + (1 - x_i)(1 - r_i)), which is 1 at x = r on the hypercube
+    /// and 0 elsewhere. Built in 2^n multiplications by doubling the table once per variable.
+    pub fn eq_poly(point: &[F]) -> Self {
+        let mut evaluations = vec![F::zero(); 1 << point.len()];
+        evaluations[0] = F::one();
+
+        for (i, r) in point.iter().enumerate() {
+            // the entries for x_i = 1 go right after those for x_i = 0
+            for j in (0..1 << i).rev() {
+                let value = evaluations[j];
+                let high = value * r;
+                evaluations[2 * j + 1] = high;
+                evaluations[2 * j] = value - high;
             }
         }
 
-        Self::new(new_evaluations)
+        Self {
+            n_vars: point.len(),
+            evaluations,
+            order: VariableOrder::MsbFirst,
+        }
+    }
+
+    /// eq(x, r) laid out in `order`, the LSB first table being the MSB first one of the
+    /// reversed point
+    pub fn eq_poly_with_order(point: &[F], order: VariableOrder) -> Self {
+        match order {
+            VariableOrder::MsbFirst => Self::eq_poly(point),
+            VariableOrder::LsbFirst => {
+                let reversed: Vec<F> = point.iter().rev().copied().collect();
+                Self::new_with_order(Self::eq_poly(&reversed).evaluations, order)
+            }
+        }
+    }
+
+    /// Fixes the first variables to `points` in place, shrinking the buffer instead of
+    /// allocating a new polynomial per variable
+    pub fn fix_variables(&mut self, points: &[F]) {
+        assert!(
+            points.len() <= self.n_vars,
+            "Cannot fix more variables than the polynomial has"
+        );
+
+        for r in points {
+            let half = self.evaluations.len() / 2;
+            for i in 0..half {
+                // x_0 is the top bit in MSB first order and the bottom bit in LSB first
+                let (low, high) = match self.order {
+                    VariableOrder::MsbFirst => (self.evaluations[i], self.evaluations[i + half]),
+                    VariableOrder::LsbFirst => {
+                        (self.evaluations[2 * i], self.evaluations[2 * i + 1])
+                    }
+                };
+                self.evaluations[i] = low + (high - low) * r;
+            }
+            self.evaluations.truncate(half);
+        }
+        self.n_vars -= points.len();
+    }
+
+    /// Full evaluation as the inner product with eq(x, point), f(r) = sum of f(b) eq(b, r)
+    pub fn evaluate_with_eq_table(&self, point: &[F]) -> F {
+        assert_eq!(
+            point.len(),
+            self.n_vars,
+            "Number of evaluation points must match the number of variables"
+        );
+
+        Self::eq_poly_with_order(point, self.order)
+            .evaluations
+            .iter()
+            .zip(self.evaluations.iter())
+            .map(|(eq, evaluation)| *eq * evaluation)
+            .sum()
+    }
+
+    pub fn add_distinct(&self, rhs: &Self) -> Self {
+        self.combine_distinct(rhs, |a, b| a + b)
     }
 
     pub fn mul_distinct(&self, rhs: &Self) -> Self {
+        self.combine_distinct(rhs, |a, b| a * b)
+    }
+
+    // g(x, y) = op(self(x), rhs(y)), the variables of self coming first
+    fn combine_distinct(&self, rhs: &Self, op: impl Fn(F, F) -> F) -> Self {
+        let rhs = rhs.to_order(self.order);
         let mut new_evaluations = Vec::new();
-        let repeat_sequence = rhs.evaluations.len();
 
-        for i in 0..self.evaluations.len() {
-            for j in 0..repeat_sequence {
-                new_evaluations.push(self.evaluations[i] * rhs.evaluations[j]);
+        match self.order {
+            VariableOrder::MsbFirst => {
+                for i in 0..self.evaluations.len() {
+                    for j in 0..rhs.evaluations.len() {
+                        new_evaluations.push(op(self.evaluations[i], rhs.evaluations[j]));
+                    }
+                }
+            }
+            VariableOrder::LsbFirst => {
+                for j in 0..rhs.evaluations.len() {
+                    for i in 0..self.evaluations.len() {
+                        new_evaluations.push(op(self.evaluations[i], rhs.evaluations[j]));
+                    }
+                }
             }
         }
 
-        Self::new(new_evaluations)
+        Self::new_with_order(new_evaluations, self.order)
     }
 
+    /// The evaluations in MSB first order, so that both layouts hash the same
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut bytes = Vec::new();
 
-        for evaluation in &self.evaluations {
+        for evaluation in &self.to_order(VariableOrder::MsbFirst).evaluations {
             bytes.extend(evaluation.into_bigint().to_bytes_be());
         }
 
@@ -65,10 +268,21 @@ impl<F: PrimeField> Multilinear<F> {
         Self::new(vec![F::zero(); 1 << num_vars])
     }
 
+    /// The sums over the hypercube with x_0 = 0 and x_0 = 1
     pub fn split_poly_into_two_and_sum_each_part(&mut self) -> Multilinear<F> {
-        let mid_point: usize = self.evaluations.len() / 2;
-        let first_half: F = self.evaluations[..mid_point].iter().sum();
-        let second_half: F = self.evaluations[mid_point..].iter().sum();
+        let (first_half, second_half) = match self.order {
+            VariableOrder::MsbFirst => {
+                let mid_point: usize = self.evaluations.len() / 2;
+                (
+                    self.evaluations[..mid_point].iter().sum(),
+                    self.evaluations[mid_point..].iter().sum(),
+                )
+            }
+            VariableOrder::LsbFirst => (
+                self.evaluations.iter().step_by(2).sum(),
+                self.evaluations.iter().skip(1).step_by(2).sum(),
+            ),
+        };
 
         Self::new(vec![first_half, second_half])
     }
@@ -83,30 +297,44 @@ impl<F: PrimeField> Multilinear<F> {
             .fold(F::zero(), |acc, val| acc + val)
     }
 
+    /// Adds variable_length + 1 variables that the polynomial does not depend on
+    /// in front of the others
     pub fn add_to_front(&self, variable_length: &usize) -> Self {
-        let new_len = 1 << variable_length;
-        let mut res = Vec::with_capacity(new_len);
+        let repeat_count = 2 << variable_length;
+        match self.order {
+            VariableOrder::MsbFirst => self.repeat_evaluations(repeat_count),
+            VariableOrder::LsbFirst => self.repeat_each_evaluation(repeat_count),
+        }
+    }
 
-        for _ in 0..new_len {
-            res.extend_from_slice(&self.evaluations);
+    /// Adds variable_length variables that the polynomial does not depend on after the others
+    pub fn add_to_back(&self, variable_length: &usize) -> Self {
+        let repeat_count = 2usize.pow(*variable_length as u32);
+        match self.order {
+            VariableOrder::MsbFirst => self.repeat_each_evaluation(repeat_count),
+            VariableOrder::LsbFirst => self.repeat_evaluations(repeat_count),
+        }
+    }
+
+    // new variables on the high bits
+    fn repeat_evaluations(&self, repeat_count: usize) -> Self {
+        let mut res = Vec::with_capacity(self.evaluations.len() * repeat_count);
+        for _ in 0..repeat_count {
             res.extend_from_slice(&self.evaluations);
         }
 
-        Self::new(res)
+        Self::new_with_order(res, self.order)
     }
 
-    pub fn add_to_back(&self, variable_length: &usize) -> Self {
-        let eval = self.evaluations.clone();
-        let input_len = eval.len();
-        let output_len = input_len * 2usize.pow(*variable_length as u32);
-        let repeat_count = output_len / input_len;
-
-        let res = eval
-            .into_iter()
-            .flat_map(|num| std::iter::repeat(num).take(repeat_count))
+    // new variables on the low bits
+    fn repeat_each_evaluation(&self, repeat_count: usize) -> Self {
+        let res = self
+            .evaluations
+            .iter()
+            .flat_map(|num| std::iter::repeat_n(*num, repeat_count))
             .collect();
 
-        Self::new(res)
+        Self::new_with_order(res, self.order)
     }
 
     pub fn duplicate_evaluation(value: &[F]) -> Self {
@@ -125,7 +353,9 @@ impl<F: PrimeField> MultilinearTrait<F> for Multilinear<F> {
 
         let mut result: Vec<F> = Vec::with_capacity(self.evaluations.len() / 2);
 
-        for (i, j) in pick_pairs_with_random_index(self.evaluations.len(), *variable_index) {
+        // the pairs are picked counting the variables from the most significant bit
+        let msb_index = self.n_vars - 1 - self.bit_of(*variable_index);
+        for (i, j) in pick_pairs_with_random_index(self.evaluations.len(), msb_index) {
             let y1: &F = &new_evaluation[i];
             let y2: &F = &new_evaluation[j];
 
@@ -137,6 +367,7 @@ impl<F: PrimeField> MultilinearTrait<F> for Multilinear<F> {
         Self {
             n_vars: self.n_vars - 1,
             evaluations: result,
+            order: self.order,
         }
     }
 
@@ -167,9 +398,7 @@ impl<F: PrimeField> MultilinearTrait<F> for Multilinear<F> {
         );
 
         let mut eval_result: Multilinear<F> = self.clone();
-        for i in 0..evaluation_points.len() {
-            eval_result = eval_result.partial_evaluation(&evaluation_points[i], &0);
-        }
+        eval_result.fix_variables(evaluation_points);
 
         eval_result.evaluations[0]
     }
@@ -179,6 +408,7 @@ impl<F: PrimeField> Add for Multilinear<F> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
+        let rhs = rhs.into_order(self.order);
         let lhs = self.evaluations;
         let mut res = vec![];
 
@@ -189,6 +419,7 @@ impl<F: PrimeField> Add for Multilinear<F> {
         Self {
             n_vars: self.n_vars,
             evaluations: res,
+            order: self.order,
         }
     }
 }
@@ -199,6 +430,7 @@ impl<F: PrimeField> AddAssign for Multilinear<F> {
         // if self.n_vars != other.n_vars {
         //     panic!("The number of variables in the two polynomials must be the same");
         // }
+        let other = other.into_order(self.order);
 
         for i in 0..self.evaluations.len() {
             self.evaluations[i] += other.evaluations[i];
@@ -210,6 +442,7 @@ impl<F: PrimeField> Sub for Multilinear<F> {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
+        let rhs = rhs.into_order(self.order);
         let lhs = self.evaluations;
         let mut res = vec![];
 
@@ -220,12 +453,14 @@ impl<F: PrimeField> Sub for Multilinear<F> {
         Self {
             n_vars: self.n_vars,
             evaluations: res,
+            order: self.order,
         }
     }
 }
 
 impl<F: PrimeField> SubAssign for Multilinear<F> {
     fn sub_assign(&mut self, other: Self) {
+        let other = other.into_order(self.order);
         for i in 0..self.evaluations.len() {
             self.evaluations[i] -= other.evaluations[i];
         }
@@ -246,6 +481,7 @@ impl<F: PrimeField> Mul<F> for Multilinear<F> {
         Self {
             n_vars: self.n_vars,
             evaluations: res,
+            order: self.order,
         }
     }
 }
@@ -255,7 +491,7 @@ mod tests {
     use field_tracker::Ft;
 
     use crate::interface::MultilinearTrait;
-    use crate::multilinear::evaluation_form::Multilinear;
+    use crate::multilinear::evaluation_form::{Multilinear, VariableOrder};
     use ark_test_curves::bls12_381::Fr as Fq_old;
 
     type Fq = Ft<4, Fq_old>;
@@ -554,4 +790,157 @@ mod tests {
         assert_eq!(duplicate, expected_poly);
         // println!("{}", Fq::summary());
     }
+
+    #[test]
+    fn test_eq_poly() {
+        let point = vec![Fq::from(2), Fq::from(3), Fq::from(5)];
+        let eq = Multilinear::eq_poly(&point);
+
+        // eq(b, r) for every b on the hypercube
+        let expected = Multilinear::from_fn(3, |index| {
+            (0..3).fold(Fq::from(1), |acc, i| {
+                if (index >> (2 - i)) & 1 == 1 {
+                    acc * point[i]
+                } else {
+                    acc * (Fq::from(1) - point[i])
+                }
+            })
+        });
+        assert_eq!(eq, expected);
+
+        // it selects the point it is built from
+        let b = vec![Fq::from(1), Fq::from(0), Fq::from(1)];
+        let selector = Multilinear::eq_poly(&b);
+        assert_eq!(
+            selector,
+            Multilinear::from_fn(3, |index| Fq::from((index == 5) as u64))
+        );
+        assert_eq!(eq.evaluation(&b), eq.evaluations[5]);
+    }
+
+    #[test]
+    fn test_fix_variables_and_eq_table_evaluation() {
+        // f(a, b, c) = 2ab + 3bc
+        let poly = Multilinear::from_fn(3, |index| {
+            let (a, b, c) = ((index >> 2) & 1, (index >> 1) & 1, index & 1);
+            Fq::from((2 * a * b + 3 * b * c) as u64)
+        });
+        let point = [Fq::from(2), Fq::from(3), Fq::from(4)];
+
+        let mut fixed = poly.clone();
+        fixed.fix_variables(&point[..1]);
+        assert_eq!(fixed, poly.partial_evaluation(&point[0], &0));
+
+        fixed.fix_variables(&point[1..]);
+        assert_eq!(fixed.n_vars, 0);
+        assert_eq!(fixed.evaluations, vec![Fq::from(48)]);
+
+        assert_eq!(poly.evaluate_with_eq_table(&point), Fq::from(48));
+        assert_eq!(poly.evaluation(&point), Fq::from(48));
+    }
+
+    #[test]
+    fn test_variable_order() {
+        // f(a, b, c) = 2ab + 3bc + c + 1
+        let f = |a: u64, b: u64, c: u64| Fq::from(2 * a * b + 3 * b * c + c + 1);
+        let msb = Multilinear::from_fn(3, |index| {
+            let bit = |i: usize| ((index >> i) & 1) as u64;
+            f(bit(2), bit(1), bit(0))
+        });
+        let lsb = msb.to_order(VariableOrder::LsbFirst);
+
+        // a is now the lowest bit
+        assert_eq!(lsb.order, VariableOrder::LsbFirst);
+        assert_eq!(lsb.evaluations[1], f(1, 0, 0));
+        assert_eq!(lsb.evaluations[4], f(0, 0, 1));
+        assert_eq!(lsb.clone().into_order(VariableOrder::MsbFirst), msb);
+        assert_eq!(lsb, msb);
+        assert_eq!(lsb.to_bytes(), msb.to_bytes());
+
+        let point = [Fq::from(2), Fq::from(3), Fq::from(4)];
+        let value = msb.evaluation(&point);
+        assert_eq!(lsb.evaluation(&point), value);
+        assert_eq!(lsb.evaluate_with_eq_table(&point), value);
+        assert_eq!(
+            Multilinear::eq_poly_with_order(&point, VariableOrder::LsbFirst),
+            Multilinear::eq_poly(&point).to_order(VariableOrder::LsbFirst)
+        );
+
+        for i in 0..3 {
+            let rest: Vec<Fq> = (0..3).filter(|j| *j != i).map(|j| point[j]).collect();
+            let partial = lsb.partial_evaluation(&point[i], &i);
+            assert_eq!(partial.order, VariableOrder::LsbFirst);
+            assert_eq!(partial.evaluation(&rest), value);
+        }
+
+        let mut fixed = lsb.clone();
+        fixed.fix_variables(&point[..2]);
+        assert_eq!(fixed.evaluation(&point[2..]), value);
+
+        assert_eq!(
+            lsb.clone().split_poly_into_two_and_sum_each_part(),
+            msb.clone().split_poly_into_two_and_sum_each_part()
+        );
+
+        // the padding variables go in front of or after the others whatever the layout
+        let nine = Fq::from(9);
+        for poly in [&msb, &lsb] {
+            let front = poly.add_to_front(&0);
+            assert_eq!(
+                front.evaluation(&[nine, point[0], point[1], point[2]]),
+                value
+            );
+            let back = poly.add_to_back(&2);
+            assert_eq!(
+                back.evaluation(&[point[0], point[1], point[2], nine, nine]),
+                value
+            );
+        }
+
+        // self's variables come first in the distinct products
+        let g = Multilinear::new(vec![Fq::from(1), Fq::from(2), Fq::from(3), Fq::from(5)]);
+        let g_point = [Fq::from(5), Fq::from(6)];
+        let product = lsb.mul_distinct(&g);
+        assert_eq!(
+            product.evaluation(&[point[0], point[1], point[2], g_point[0], g_point[1]]),
+            value * g.evaluation(&g_point)
+        );
+        assert_eq!(
+            (lsb.clone() - msb.clone()),
+            Multilinear::new_with_order(vec![Fq::from(0); 8], VariableOrder::LsbFirst)
+        );
+    }
+
+    #[test]
+    fn test_permute_variables() {
+        let poly = Multilinear::new(vec![
+            Fq::from(3),
+            Fq::from(9),
+            Fq::from(7),
+            Fq::from(13),
+            Fq::from(6),
+            Fq::from(12),
+            Fq::from(10),
+            Fq::from(18),
+        ]);
+        let point = [Fq::from(2), Fq::from(3), Fq::from(4)];
+        let value = poly.evaluation(&point);
+
+        // variable i of the result is variable permutation[i] of the original
+        for original in [poly.clone(), poly.to_order(VariableOrder::LsbFirst)] {
+            let permuted = original.permute_variables(&[2, 0, 1]);
+            assert_eq!(permuted.evaluation(&[point[2], point[0], point[1]]), value);
+            assert_eq!(original.permute_variables(&[0, 1, 2]), original);
+        }
+
+        // reversing only reinterprets the evaluations
+        let reversed = poly.reverse_variables();
+        assert_eq!(reversed.evaluations, poly.evaluations);
+        assert_ne!(reversed, poly);
+        assert_eq!(reversed.evaluation(&[point[2], point[1], point[0]]), value);
+        assert_eq!(
+            reversed.to_order(VariableOrder::MsbFirst),
+            poly.permute_variables(&[2, 1, 0])
+        );
+    }
 }
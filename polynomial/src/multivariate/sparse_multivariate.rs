@@ -0,0 +1,481 @@
+use crate::{
+    utils::{mobius_transform, zeta_transform},
+    DenseUnivariatePolynomial, Multilinear, UnivariatePolynomialTrait, VariableOrder,
+};
+use ark_ff::PrimeField;
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter, Result},
+    ops::{Add, Mul, Neg, Sub},
+};
+
+/// coeff * x_0^exponents[0] * x_1^exponents[1] * ...
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultivariateMonomial<F: PrimeField> {
+    pub coeff: F,
+    pub exponents: Vec<usize>,
+}
+
+/// A sum of monomials with arbitrary exponents in `n_vars` variables.
+///
+/// The terms are kept canonical: like terms merged, no zero coefficients, sorted by exponents,
+/// so two polynomials are equal exactly when their terms are.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseMultivariatePolynomial<F: PrimeField> {
+    pub n_vars: usize,
+    terms: Vec<MultivariateMonomial<F>>,
+}
+
+impl<F: PrimeField> MultivariateMonomial<F> {
+    pub fn new(coeff: F, exponents: Vec<usize>) -> Self {
+        MultivariateMonomial { coeff, exponents }
+    }
+
+    pub fn degree(&self) -> usize {
+        self.exponents.iter().sum()
+    }
+
+    pub fn evaluate(&self, point: &[F]) -> F {
+        self.exponents
+            .iter()
+            .zip(point.iter())
+            .fold(self.coeff, |acc, (exponent, x)| {
+                acc * x.pow([*exponent as u64])
+            })
+    }
+}
+
+impl<F: PrimeField> SparseMultivariatePolynomial<F> {
+    pub fn new(n_vars: usize, terms: Vec<MultivariateMonomial<F>>) -> Self {
+        for term in terms.iter() {
+            assert_eq!(
+                term.exponents.len(),
+                n_vars,
+                "Every monomial should have an exponent per variable"
+            );
+        }
+
+        Self::from_map(n_vars, terms.into_iter().map(|t| (t.exponents, t.coeff)))
+    }
+
+    pub fn zero(n_vars: usize) -> Self {
+        SparseMultivariatePolynomial {
+            n_vars,
+            terms: vec![],
+        }
+    }
+
+    pub fn constant(n_vars: usize, value: F) -> Self {
+        Self::new(
+            n_vars,
+            vec![MultivariateMonomial::new(value, vec![0; n_vars])],
+        )
+    }
+
+    /// The polynomial x_i
+    pub fn variable(n_vars: usize, index: usize) -> Self {
+        assert!(index < n_vars, "Variable index out of range");
+
+        let mut exponents = vec![0; n_vars];
+        exponents[index] = 1;
+        Self::new(n_vars, vec![MultivariateMonomial::new(F::one(), exponents)])
+    }
+
+    // merges like terms and drops the zero ones
+    fn from_map(n_vars: usize, terms: impl Iterator<Item = (Vec<usize>, F)>) -> Self {
+        let mut merged: BTreeMap<Vec<usize>, F> = BTreeMap::new();
+        for (exponents, coeff) in terms {
+            *merged.entry(exponents).or_insert(F::zero()) += coeff;
+        }
+
+        let terms = merged
+            .into_iter()
+            .filter(|(_, coeff)| !coeff.is_zero())
+            .map(|(exponents, coeff)| MultivariateMonomial::new(coeff, exponents))
+            .collect();
+
+        SparseMultivariatePolynomial { n_vars, terms }
+    }
+
+    pub fn terms(&self) -> &[MultivariateMonomial<F>] {
+        &self.terms
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// The total degree, the largest sum of exponents of a term
+    pub fn degree(&self) -> usize {
+        self.terms
+            .iter()
+            .map(|term| term.degree())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The degree in the variable x_i alone, which bounds the sumcheck round polynomials
+    pub fn degree_in(&self, index: usize) -> usize {
+        self.terms
+            .iter()
+            .map(|term| term.exponents[index])
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn is_multilinear(&self) -> bool {
+        (0..self.n_vars).all(|i| self.degree_in(i) <= 1)
+    }
+
+    pub fn evaluate(&self, point: &[F]) -> F {
+        assert_eq!(
+            point.len(),
+            self.n_vars,
+            "Number of evaluation points must match the number of variables"
+        );
+
+        self.terms.iter().map(|term| term.evaluate(point)).sum()
+    }
+
+    /// Fixes x_i = value, the result has one variable less
+    pub fn partial_evaluation(&self, value: &F, variable_index: &usize) -> Self {
+        assert!(*variable_index < self.n_vars, "Variable index out of range");
+
+        Self::from_map(
+            self.n_vars - 1,
+            self.terms.iter().map(|term| {
+                let mut exponents = term.exponents.clone();
+                let exponent = exponents.remove(*variable_index);
+                (exponents, term.coeff * value.pow([exponent as u64]))
+            }),
+        )
+    }
+
+    /// Fixes the variables one after the other, indices referring to the remaining variables
+    /// like `Multilinear::partial_evaluations`
+    pub fn partial_evaluations(&self, points: &[F], variable_indices: &[usize]) -> Self {
+        assert_eq!(
+            points.len(),
+            variable_indices.len(),
+            "The length of evaluation_points and variable_indices should be the same"
+        );
+
+        points
+            .iter()
+            .zip(variable_indices.iter())
+            .fold(self.clone(), |poly, (point, index)| {
+                poly.partial_evaluation(point, index)
+            })
+    }
+
+    /// The sum over {0, 1}^n: on the hypercube x^e = x for e > 0, so a term with z zero
+    /// exponents sums to coeff * 2^z
+    pub fn sum_over_the_boolean_hypercube(&self) -> F {
+        self.terms
+            .iter()
+            .map(|term| term.coeff * F::from(2u64).pow([count_zeros(&term.exponents[..]) as u64]))
+            .sum()
+    }
+
+    /// The sumcheck round polynomial in the first variable,
+    /// g(X) = sum over b in {0, 1}^(n - 1) of f(X, b)
+    pub fn round_polynomial(&self) -> DenseUnivariatePolynomial<F> {
+        assert!(self.n_vars > 0, "There is no variable left");
+
+        let mut coefficients = vec![F::zero(); self.degree_in(0) + 1];
+        for term in self.terms.iter() {
+            let zeros = count_zeros(&term.exponents[1..]);
+            coefficients[term.exponents[0]] += term.coeff * F::from(2u64).pow([zeros as u64]);
+        }
+
+        DenseUnivariatePolynomial::new(coefficients)
+    }
+
+    /// The multilinear extension of the values over the boolean hypercube, which reduces
+    /// every x^e with e > 0 to x. Variable x_0 is the most significant bit of the index,
+    /// as in `Multilinear`.
+    pub fn multilinear_extension(&self) -> Multilinear<F> {
+        let mut values = vec![F::zero(); 1 << self.n_vars];
+        for term in self.terms.iter() {
+            let mask = term
+                .exponents
+                .iter()
+                .fold(0, |mask, exponent| (mask << 1) | (*exponent > 0) as usize);
+            values[mask] += term.coeff;
+        }
+
+        zeta_transform(&mut values);
+        Multilinear::new(values)
+    }
+
+    /// The multilinear polynomial with these evaluations over the boolean hypercube
+    pub fn from_multilinear(poly: &Multilinear<F>) -> Self {
+        let mut coefficients = poly.to_order(VariableOrder::MsbFirst).evaluations;
+        mobius_transform(&mut coefficients);
+
+        let n_vars = poly.n_vars;
+        Self::from_map(
+            n_vars,
+            coefficients.into_iter().enumerate().map(|(mask, coeff)| {
+                let exponents = (0..n_vars)
+                    .map(|i| (mask >> (n_vars - 1 - i)) & 1)
+                    .collect();
+                (exponents, coeff)
+            }),
+        )
+    }
+}
+
+fn count_zeros(exponents: &[usize]) -> usize {
+    exponents.iter().filter(|exponent| **exponent == 0).count()
+}
+
+impl<F: PrimeField> Add for SparseMultivariatePolynomial<F> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        assert_eq!(
+            self.n_vars, rhs.n_vars,
+            "The number of variables in the two polynomials must be the same"
+        );
+
+        Self::from_map(
+            self.n_vars,
+            self.terms
+                .into_iter()
+                .chain(rhs.terms)
+                .map(|term| (term.exponents, term.coeff)),
+        )
+    }
+}
+
+impl<F: PrimeField> Neg for SparseMultivariatePolynomial<F> {
+    type Output = Self;
+
+    fn neg(mut self) -> Self::Output {
+        for term in self.terms.iter_mut() {
+            term.coeff = -term.coeff;
+        }
+        self
+    }
+}
+
+impl<F: PrimeField> Sub for SparseMultivariatePolynomial<F> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+impl<F: PrimeField> Mul for SparseMultivariatePolynomial<F> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        assert_eq!(
+            self.n_vars, rhs.n_vars,
+            "The number of variables in the two polynomials must be the same"
+        );
+
+        let products = self.terms.iter().flat_map(|lhs| {
+            rhs.terms.iter().map(move |rhs| {
+                let exponents = lhs
+                    .exponents
+                    .iter()
+                    .zip(rhs.exponents.iter())
+                    .map(|(a, b)| a + b)
+                    .collect();
+                (exponents, lhs.coeff * rhs.coeff)
+            })
+        });
+
+        Self::from_map(self.n_vars, products)
+    }
+}
+
+impl<F: PrimeField> Mul<F> for SparseMultivariatePolynomial<F> {
+    type Output = Self;
+
+    fn mul(self, rhs: F) -> Self::Output {
+        Self::from_map(
+            self.n_vars,
+            self.terms
+                .into_iter()
+                .map(|term| (term.exponents, term.coeff * rhs)),
+        )
+    }
+}
+
+impl<F: PrimeField> Display for SparseMultivariatePolynomial<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if self.terms.is_empty() {
+            return write!(f, "0");
+        }
+
+        for (i, term) in self.terms.iter().enumerate() {
+            if i > 0 {
+                write!(f, " + ")?;
+            }
+            write!(f, "{}", term.coeff)?;
+
+            for (j, exponent) in term.exponents.iter().enumerate() {
+                match exponent {
+                    0 => {}
+                    1 => write!(f, "x_{}", j)?,
+                    _ => write!(f, "x_{}^{}", j, exponent)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::MultilinearTrait;
+    use ark_test_curves::bls12_381::Fr;
+
+    // 3x^2y + 2yz^3 + 5
+    fn init() -> SparseMultivariatePolynomial<Fr> {
+        SparseMultivariatePolynomial::new(
+            3,
+            vec![
+                MultivariateMonomial::new(Fr::from(3), vec![2, 1, 0]),
+                MultivariateMonomial::new(Fr::from(2), vec![0, 1, 3]),
+                MultivariateMonomial::new(Fr::from(5), vec![0, 0, 0]),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_evaluation_and_degrees() {
+        let poly = init();
+
+        // 3 * 4 * 3 + 2 * 3 * 64 + 5
+        assert_eq!(
+            poly.evaluate(&[Fr::from(2), Fr::from(3), Fr::from(4)]),
+            Fr::from(425)
+        );
+        assert_eq!(poly.degree(), 4);
+        assert_eq!(poly.degree_in(0), 2);
+        assert_eq!(poly.degree_in(1), 1);
+        assert_eq!(poly.degree_in(2), 3);
+        assert!(!poly.is_multilinear());
+
+        // like terms merge and cancel
+        let poly = SparseMultivariatePolynomial::new(
+            2,
+            vec![
+                MultivariateMonomial::new(Fr::from(3), vec![1, 1]),
+                MultivariateMonomial::new(Fr::from(-3), vec![1, 1]),
+            ],
+        );
+        assert!(poly.is_zero());
+        assert_eq!(poly, SparseMultivariatePolynomial::zero(2));
+    }
+
+    #[test]
+    fn test_partial_evaluation() {
+        let poly = init();
+
+        // y = 3: 9x^2 + 6z^3 + 5
+        let expected = SparseMultivariatePolynomial::new(
+            2,
+            vec![
+                MultivariateMonomial::new(Fr::from(9), vec![2, 0]),
+                MultivariateMonomial::new(Fr::from(6), vec![0, 3]),
+                MultivariateMonomial::new(Fr::from(5), vec![0, 0]),
+            ],
+        );
+        assert_eq!(poly.partial_evaluation(&Fr::from(3), &1), expected);
+
+        let partial = poly.partial_evaluations(&[Fr::from(2), Fr::from(4)], &[0, 1]);
+        assert_eq!(partial.n_vars, 1);
+        assert_eq!(
+            partial.evaluate(&[Fr::from(3)]),
+            poly.evaluate(&[Fr::from(2), Fr::from(3), Fr::from(4)])
+        );
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let (x, y) = (
+            SparseMultivariatePolynomial::<Fr>::variable(2, 0),
+            SparseMultivariatePolynomial::<Fr>::variable(2, 1),
+        );
+        let one = SparseMultivariatePolynomial::constant(2, Fr::from(1));
+
+        // (x + y)(x - y) = x^2 - y^2
+        let product = (x.clone() + y.clone()) * (x.clone() - y.clone());
+        let expected = x.clone() * x.clone() - y.clone() * y.clone();
+        assert_eq!(product, expected);
+        assert_eq!(product.terms().len(), 2);
+
+        // (x y + 1)^2 at (2, 5)
+        let square = (x.clone() * y.clone() + one.clone()) * (x * y + one);
+        assert_eq!(square.evaluate(&[Fr::from(2), Fr::from(5)]), Fr::from(121));
+        assert_eq!(
+            (square.clone() * Fr::from(2)).evaluate(&[Fr::from(2), Fr::from(5)]),
+            Fr::from(242)
+        );
+        assert!((square.clone() - square).is_zero());
+    }
+
+    #[test]
+    fn test_multilinear_extension() {
+        let poly = init();
+        let mle = poly.multilinear_extension();
+
+        // agrees with the polynomial on the hypercube
+        for (i, evaluation) in mle.evaluations.iter().enumerate() {
+            let point: Vec<Fr> = (0..3)
+                .map(|j| Fr::from(((i >> (2 - j)) & 1) as u64))
+                .collect();
+            assert_eq!(*evaluation, poly.evaluate(&point));
+        }
+
+        // 3xy + 2yz + 5, which is multilinear and has the same extension
+        let reduced = SparseMultivariatePolynomial::from_multilinear(&mle);
+        assert!(reduced.is_multilinear());
+        let expected = SparseMultivariatePolynomial::new(
+            3,
+            vec![
+                MultivariateMonomial::new(Fr::from(3), vec![1, 1, 0]),
+                MultivariateMonomial::new(Fr::from(2), vec![0, 1, 1]),
+                MultivariateMonomial::new(Fr::from(5), vec![0, 0, 0]),
+            ],
+        );
+        assert_eq!(reduced, expected);
+
+        let point = [Fr::from(7), Fr::from(8), Fr::from(9)];
+        assert_eq!(mle.evaluation(&point), reduced.evaluate(&point));
+    }
+
+    #[test]
+    fn test_sumcheck_round() {
+        let poly = init();
+
+        let sum: Fr = (0..8usize)
+            .map(|i| {
+                let point: Vec<Fr> = (0..3)
+                    .map(|j| Fr::from(((i >> (2 - j)) & 1) as u64))
+                    .collect();
+                poly.evaluate(&point)
+            })
+            .sum();
+        assert_eq!(poly.sum_over_the_boolean_hypercube(), sum);
+
+        // g(0) + g(1) is the claimed sum, and g(r) the next claim
+        let round = poly.round_polynomial();
+        assert_eq!(round.degree(), poly.degree_in(0));
+        assert_eq!(
+            round.evaluate(Fr::from(0)) + round.evaluate(Fr::from(1)),
+            sum
+        );
+
+        let r = Fr::from(11);
+        let next = poly.partial_evaluation(&r, &0);
+        assert_eq!(round.evaluate(r), next.sum_over_the_boolean_hypercube());
+    }
+}
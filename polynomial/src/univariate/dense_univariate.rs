@@ -1,16 +1,24 @@
 use crate::{
-    utils::{
-        convert_prime_field_to_f64, dense_langrange_basis, fft, remove_trailing_and_redundant_zeros,
-    },
+    univariate::{domain::Domain, subproduct_tree::SubproductTree},
+    utils::{dense_langrange_basis, remove_trailing_and_redundant_zeros},
     UnivariatePolynomialTrait,
 };
-use ark_ff::{BigInteger, PrimeField, Zero};
-use num_complex::{Complex, Complex64};
+use ark_ff::{BigInteger, PrimeField};
 use std::{
     fmt::{Display, Formatter, Result},
     ops::{Add, AddAssign, Div, Index, IndexMut, Mul, Neg, Rem, Sub, SubAssign},
 };
 
+// Below this many coefficients in the quotient or the divisor, long division is faster
+const FAST_DIVISION_THRESHOLD: usize = 64;
+
+// Up to this many points Lagrange interpolation and Horner evaluation are faster
+pub(crate) const FAST_INTERPOLATION_THRESHOLD: usize = 64;
+
+// Below this many coefficients in the smaller factor, schoolbook multiplication
+// beats the three transforms of the NTT
+const SCHOOLBOOK_THRESHOLD: usize = 32;
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct DenseUnivariatePolynomial<F: PrimeField> {
     pub coefficients: Vec<F>,
@@ -74,6 +82,10 @@ impl<F: PrimeField> DenseUnivariatePolynomial<F> {
     }
 
     pub fn interpolate(point_ys: Vec<F>, point_xs: Vec<F>) -> Self {
+        if point_xs.len() > FAST_INTERPOLATION_THRESHOLD {
+            return SubproductTree::new(&point_xs).interpolate(&point_ys);
+        }
+
         let langrange_poly_vec = dense_langrange_basis(&point_xs, &point_ys);
         let langrange_poly = langrange_poly_vec
             .iter()
@@ -84,6 +96,56 @@ impl<F: PrimeField> DenseUnivariatePolynomial<F> {
         langrange_poly
     }
 
+    /// The evaluations at every point, through a subproduct tree when there are many of them
+    pub fn evaluate_many(&self, points: &[F]) -> Vec<F> {
+        if points.len() <= FAST_INTERPOLATION_THRESHOLD {
+            return points.iter().map(|point| self.evaluate(*point)).collect();
+        }
+
+        SubproductTree::new(points).evaluate(self)
+    }
+
+    pub fn derivative(&self) -> Self {
+        let coefficients = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, coeff)| F::from(i as u64) * coeff)
+            .collect();
+
+        Self::new(coefficients)
+    }
+
+    /// g with self * g = 1 mod x^n, by Newton iteration: g_2k = g_k (2 - self * g_k) mod x^2k.
+    /// Returns `None` when the constant coefficient is zero.
+    pub fn inverse_mod_x_power(&self, n: usize) -> Option<Self> {
+        let mut inverse = vec![self.coefficients.first()?.inverse()?];
+
+        let mut precision = 1;
+        while precision < n {
+            precision = (2 * precision).min(n);
+
+            let f = Self::new(self.coefficients[..precision.min(self.coefficients.len())].to_vec());
+            let g = Self::new(inverse.clone());
+
+            // e = self * g - 1 = 0 mod x^k, and g - g * e is correct to twice the precision
+            let mut e = (f * g.clone()).coefficients;
+            e.resize(precision, F::zero());
+            e[0] -= F::one();
+            let mut correction = (g * Self::new(e)).coefficients;
+            correction.resize(precision, F::zero());
+
+            inverse.resize(precision, F::zero());
+            for (coeff, correction) in inverse.iter_mut().zip(correction.iter()) {
+                *coeff -= correction;
+            }
+        }
+
+        inverse.truncate(n);
+        Some(Self::new(inverse))
+    }
+
     /// This function is used for poly division, returning the quotient and remainder
     pub fn divide_with_q_and_r(
         &self,
@@ -98,6 +160,9 @@ impl<F: PrimeField> DenseUnivariatePolynomial<F> {
             panic!("Dividing by zero polynomial")
         } else if self.degree() < divisor.degree() {
             Some((DenseUnivariatePolynomial::zero(), self.clone().into()))
+        } else if divisor.degree().min(self.degree() - divisor.degree()) >= FAST_DIVISION_THRESHOLD
+        {
+            self.fast_divide_with_q_and_r(divisor)
         } else {
             // Now we know that self.degree() >= divisor.degree();
             let mut quotient = vec![F::zero(); self.degree() - divisor.degree() + 1];
@@ -126,53 +191,105 @@ impl<F: PrimeField> DenseUnivariatePolynomial<F> {
     // (3xy + 2x + 4z + 3) (2xy + 3z + 4)
     // 6x^2y^2 .....+ 25z + 12 // 8
 
-    pub fn fft_mult_poly(
-        polya: &DenseUnivariatePolynomial<F>,
-        polyb: &DenseUnivariatePolynomial<F>,
-    ) -> Self {
-        let poly1 = polya.coefficients.clone();
-        let poly2 = polyb.coefficients.clone();
+    /// Division in O(M(n)): reversing the coefficients turns a = bq + r into
+    /// rev(a) = rev(b) rev(q) mod x^(m - n + 1), so rev(q) = rev(a) rev(b)^(-1) and r = a - bq
+    pub fn fast_divide_with_q_and_r(
+        &self,
+        divisor: &Self,
+    ) -> Option<(DenseUnivariatePolynomial<F>, DenseUnivariatePolynomial<F>)> {
+        if self.is_zero() {
+            return Some((
+                DenseUnivariatePolynomial::zero(),
+                DenseUnivariatePolynomial::zero(),
+            ));
+        } else if divisor.is_zero() {
+            panic!("Dividing by zero polynomial")
+        } else if self.degree() < divisor.degree() {
+            return Some((DenseUnivariatePolynomial::zero(), self.clone()));
+        }
 
-        let coefficient_length_of_resultant_poly = poly1.len() + poly2.len() - 1;
+        let dividend = self.remove_leading_zeros();
+        let divisor = divisor.remove_leading_zeros();
+        let quotient_len = dividend.degree() - divisor.degree() + 1;
 
-        let coefficient_length_of_resultant_poly_pow_of_2 =
-            coefficient_length_of_resultant_poly.next_power_of_two();
+        let reversed_dividend: Vec<F> = dividend.coefficients.iter().rev().copied().collect();
+        let reversed_divisor: Vec<F> = divisor.coefficients.iter().rev().copied().collect();
+        let divisor_inverse = Self::new(reversed_divisor).inverse_mod_x_power(quotient_len)?;
 
-        let mut poly1_in_complex_form: Vec<Complex64> = poly1
-            .iter()
-            .map(|&x| Complex64::new(convert_prime_field_to_f64(x), 0.0))
-            .collect();
-        let mut poly2_in_complex_form: Vec<Complex64> = poly2
+        let mut quotient =
+            (Self::new(reversed_dividend[..quotient_len].to_vec()) * divisor_inverse).coefficients;
+        quotient.resize(quotient_len, F::zero());
+        quotient.reverse();
+        let quotient = Self::new(quotient);
+
+        let product = (divisor.clone() * quotient.clone()).coefficients;
+        let remainder: Vec<F> = dividend
+            .coefficients
             .iter()
-            .map(|&x| Complex64::new(convert_prime_field_to_f64(x), 0.0))
+            .zip(product.iter())
+            .take(divisor.degree())
+            .map(|(a, b)| *a - b)
             .collect();
 
-        poly1_in_complex_form.resize(
-            coefficient_length_of_resultant_poly_pow_of_2,
-            Complex64::new(0.0, 0.0),
-        );
-        poly2_in_complex_form.resize(
-            coefficient_length_of_resultant_poly_pow_of_2,
-            Complex64::new(0.0, 0.0),
-        );
+        Some((
+            quotient,
+            Self::new(remove_trailing_and_redundant_zeros(&remainder)),
+        ))
+    }
 
-        let fft_poly1 = fft(&poly1_in_complex_form, false);
-        let fft_poly2 = fft(&poly2_in_complex_form, false);
+    /// Exact product through the number theoretic transform over a power of two `Domain`.
+    /// Falls back to schoolbook multiplication when the field has no root of unity of that order.
+    pub fn fft_mult_poly(
+        polya: &DenseUnivariatePolynomial<F>,
+        polyb: &DenseUnivariatePolynomial<F>,
+    ) -> Self {
+        if polya.coefficients.is_empty() || polyb.coefficients.is_empty() {
+            return Self::new(vec![]);
+        }
 
-        let mut element_wise_product = vec![Complex::zero(); fft_poly1.len()];
-        for i in 0..fft_poly1.len() {
-            element_wise_product[i] = fft_poly1[i] * fft_poly2[i];
+        let coefficient_length_of_resultant_poly =
+            polya.coefficients.len() + polyb.coefficients.len() - 1;
+        let size = coefficient_length_of_resultant_poly.next_power_of_two();
+        if size.trailing_zeros() > F::TWO_ADICITY {
+            return Self::schoolbook_mult_poly(polya, polyb);
         }
 
-        let inverse_fft = fft(&element_wise_product, true);
+        // pointwise products don't care about the order of the evaluations,
+        // so the bit reversal is skipped in both directions
+        let domain = Domain::<F>::new(size);
+        let mut poly1 = polya.coefficients.clone();
+        let mut poly2 = polyb.coefficients.clone();
+        domain.fft_bit_reversed(&mut poly1);
+        domain.fft_bit_reversed(&mut poly2);
 
-        let result: Vec<F> = inverse_fft
+        let mut element_wise_product: Vec<F> = poly1
             .iter()
-            .take(coefficient_length_of_resultant_poly)
-            .map(|i| F::from(i.re.round() as u64))
+            .zip(poly2.iter())
+            .map(|(a, b)| *a * b)
             .collect();
+        domain.ifft_bit_reversed(&mut element_wise_product);
+        element_wise_product.truncate(coefficient_length_of_resultant_poly);
+
+        Self::new(element_wise_product)
+    }
+
+    /// O(nm) multiplication, faster than the transform for small polynomials
+    pub fn schoolbook_mult_poly(
+        polya: &DenseUnivariatePolynomial<F>,
+        polyb: &DenseUnivariatePolynomial<F>,
+    ) -> Self {
+        if polya.coefficients.is_empty() || polyb.coefficients.is_empty() {
+            return Self::new(vec![]);
+        }
+
+        let mut product = vec![F::zero(); polya.coefficients.len() + polyb.coefficients.len() - 1];
+        for (i, a) in polya.coefficients.iter().enumerate() {
+            for (j, b) in polyb.coefficients.iter().enumerate() {
+                product[i + j] += *a * b;
+            }
+        }
 
-        Self::new(result)
+        Self::new(product)
     }
 }
 
@@ -216,19 +333,15 @@ impl<F: PrimeField> Mul for DenseUnivariatePolynomial<F> {
             return DenseUnivariatePolynomial::new(vec![]);
         }
 
-        // Create a new polynomial with the degree of the two polynomials
-        let poly_product_degree = self.degree() + other.degree();
+        // d + 1 coefficients represent a polynomial of degree d
+        let poly_a = DenseUnivariatePolynomial::new(self.coefficients[..=self.degree()].to_vec());
+        let poly_b = DenseUnivariatePolynomial::new(other.coefficients[..=other.degree()].to_vec());
 
-        // during poly mul we would need d + 1 element to represent a polynomial of degree d
-        let mut poly_product_coefficients = vec![F::zero(); poly_product_degree + 1];
-
-        for i in 0..=self.degree() {
-            for j in 0..=other.degree() {
-                poly_product_coefficients[i + j] += self.coefficients[i] * other.coefficients[j];
-            }
+        if poly_a.coefficients.len().min(poly_b.coefficients.len()) <= SCHOOLBOOK_THRESHOLD {
+            DenseUnivariatePolynomial::schoolbook_mult_poly(&poly_a, &poly_b)
+        } else {
+            DenseUnivariatePolynomial::fft_mult_poly(&poly_a, &poly_b)
         }
-
-        DenseUnivariatePolynomial::new(poly_product_coefficients)
     }
 }
 
@@ -666,27 +779,137 @@ mod tests {
 
     #[test]
     fn test_fft_multiplication_2() {
-        // (1 + 2x + 3x^2) * (4 + 5x + 6x^2)
-        // 4 * (1 + 2x + 3x^2) + 5x * (1 + 2x + 3x^2) + 6x^2 * (1 + 2x + 3x^2)
-        // 4 + 8x + 12x^2 + 5x + 10x^2 + 15x^3 + 6x^2 + 12x^3 + 18x^4
-        // 4 + 13x + 28x^2 + 27x^3 + 18x^4
-        // [4, 13, 28, 27, 18]
-        let tt: Vec<Fr> = generate_random_numbers(7);
-        let poly_a: DenseUnivariatePolynomial<Fr> = DenseUnivariatePolynomial::new(tt);
-        // println!("poly_a={:?}", poly_a);
+        // full width coefficients, which a floating point FFT can't multiply exactly
+        let poly_a: DenseUnivariatePolynomial<Fr> =
+            DenseUnivariatePolynomial::new(generate_random_numbers(7));
         let poly_b: DenseUnivariatePolynomial<Fr> =
             DenseUnivariatePolynomial::new(generate_random_numbers(10));
-        // println!("poly_b={:?}", poly_b);
         let result = DenseUnivariatePolynomial::fft_mult_poly(&poly_a, &poly_b);
-        // println!("result={:?}", result);
-        // let expected_result = DenseUnivariatePolynomial::new(vec![
-        //     Fr::from(4),
-        //     Fr::from(13),
-        //     Fr::from(28),
-        //     Fr::from(27),
-        //     Fr::from(18),
-        // ]);
-
-        // assert_eq!(result, expected_result);
+
+        assert_eq!(
+            result,
+            DenseUnivariatePolynomial::schoolbook_mult_poly(&poly_a, &poly_b)
+        );
+
+        // and the product agrees with the factors everywhere
+        let point = Fr::from(123456789u64);
+        assert_eq!(
+            result.evaluate(point),
+            poly_a.evaluate(point) * poly_b.evaluate(point)
+        );
+    }
+
+    #[test]
+    fn test_ntt_matches_schoolbook() {
+        // lengths on both sides of SCHOOLBOOK_THRESHOLD and of powers of two
+        let lengths = [1, 2, 3, 16, 31, 32, 33, 64, 65, 100];
+        for len_a in lengths {
+            for len_b in lengths {
+                let poly_a: DenseUnivariatePolynomial<Fr> =
+                    DenseUnivariatePolynomial::new(generate_random_numbers(len_a));
+                let poly_b: DenseUnivariatePolynomial<Fr> =
+                    DenseUnivariatePolynomial::new(generate_random_numbers(len_b));
+
+                let expected = DenseUnivariatePolynomial::schoolbook_mult_poly(&poly_a, &poly_b);
+                assert_eq!(expected.coefficients.len(), len_a + len_b - 1);
+                assert_eq!(
+                    DenseUnivariatePolynomial::fft_mult_poly(&poly_a, &poly_b),
+                    expected
+                );
+                assert_eq!(poly_a * poly_b, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fft_multiplication_without_roots_of_unity() {
+        use field::small_prime_field::SmallPrimeField;
+        type Mersenne61 = SmallPrimeField<2305843009213693951>;
+
+        // p - 1 = 2 * odd, so only a size 2 domain exists and the schoolbook method is used
+        let poly_a = DenseUnivariatePolynomial::new(generate_random_numbers::<Mersenne61>(40));
+        let poly_b = DenseUnivariatePolynomial::new(generate_random_numbers::<Mersenne61>(50));
+
+        assert_eq!(
+            DenseUnivariatePolynomial::fft_mult_poly(&poly_a, &poly_b),
+            DenseUnivariatePolynomial::schoolbook_mult_poly(&poly_a, &poly_b)
+        );
+        assert_eq!(
+            poly_a.clone() * poly_b.clone(),
+            DenseUnivariatePolynomial::schoolbook_mult_poly(&poly_a, &poly_b)
+        );
+    }
+
+    #[test]
+    fn test_inverse_mod_x_power() {
+        let poly: DenseUnivariatePolynomial<Fr> =
+            DenseUnivariatePolynomial::new(generate_random_numbers(20));
+        for n in [1, 2, 7, 16, 50] {
+            let inverse = poly.inverse_mod_x_power(n).unwrap();
+            let mut product = (poly.clone() * inverse).coefficients;
+            product.truncate(n);
+
+            let mut one = vec![Fr::from(0); n];
+            one[0] = Fr::from(1);
+            assert_eq!(product, one);
+        }
+
+        // x + x^2 has no inverse
+        let poly = DenseUnivariatePolynomial::new(vec![Fr::from(0), Fr::from(1), Fr::from(1)]);
+        assert!(poly.inverse_mod_x_power(4).is_none());
+    }
+
+    #[test]
+    fn test_fast_division_matches_long_division() {
+        for (dividend_len, divisor_len) in [(10, 3), (10, 10), (10, 11), (200, 70), (300, 1)] {
+            let dividend: DenseUnivariatePolynomial<Fr> =
+                DenseUnivariatePolynomial::new(generate_random_numbers(dividend_len));
+            let divisor: DenseUnivariatePolynomial<Fr> =
+                DenseUnivariatePolynomial::new(generate_random_numbers(divisor_len));
+
+            let (quotient, remainder) = dividend.fast_divide_with_q_and_r(&divisor).unwrap();
+            assert_eq!(
+                dividend.divide_with_q_and_r(&divisor).unwrap(),
+                (quotient.clone(), remainder.clone())
+            );
+
+            // a = bq + r with deg r < deg b
+            assert!(remainder.is_zero() || remainder.degree() < divisor.degree());
+            let point = Fr::from(987654321u64);
+            assert_eq!(
+                dividend.evaluate(point),
+                divisor.evaluate(point) * quotient.evaluate(point) + remainder.evaluate(point)
+            );
+        }
+
+        // an exact division leaves no remainder
+        let a: DenseUnivariatePolynomial<Fr> =
+            DenseUnivariatePolynomial::new(generate_random_numbers(100));
+        let b: DenseUnivariatePolynomial<Fr> =
+            DenseUnivariatePolynomial::new(generate_random_numbers(80));
+        let (quotient, remainder) = (a.clone() * b.clone()).divide_with_q_and_r(&b).unwrap();
+        assert_eq!(quotient, a);
+        assert!(remainder.is_zero());
+    }
+
+    #[test]
+    fn test_multipoint_evaluation_and_fast_interpolation() {
+        let poly: DenseUnivariatePolynomial<Fr> =
+            DenseUnivariatePolynomial::new(generate_random_numbers(300));
+        let points: Vec<Fr> = generate_random_numbers(300);
+
+        let evaluations = poly.evaluate_many(&points);
+        for (point, evaluation) in points.iter().zip(evaluations.iter()) {
+            assert_eq!(poly.evaluate(*point), *evaluation);
+        }
+
+        assert_eq!(
+            DenseUnivariatePolynomial::interpolate(evaluations, points),
+            poly
+        );
+
+        let poly = DenseUnivariatePolynomial::new(vec![Fr::from(1), Fr::from(2), Fr::from(3)]);
+        let derivative = DenseUnivariatePolynomial::new(vec![Fr::from(2), Fr::from(6)]);
+        assert_eq!(poly.derivative(), derivative);
     }
 }
@@ -1,6 +1,6 @@
-use crate::utils::{compute_complex_form, fft, serial_fft};
-use ark_ff::{FftField, PrimeField};
-use num_complex::Complex64;
+use crate::utils::{mixed_radix_fft, serial_fft, serial_fft_dif, serial_fft_dit};
+use ark_ff::{batch_inversion, FftField, PrimeField};
+use num_bigint::BigUint;
 
 #[derive(Clone, PartialEq, Eq, Default, Debug)]
 pub struct Domain<F: FftField> {
@@ -12,6 +12,10 @@ pub struct Domain<F: FftField> {
     pub(crate) group_gen_inverse: F,
     /// This is the inverse of the group size
     pub(crate) group_size_inverse: F,
+    /// The domain is the coset offset * <omega>, one for the subgroup itself
+    pub(crate) offset: F,
+    /// This is the inverse of the coset offset
+    pub(crate) offset_inverse: F,
 }
 
 // implemenat the display trait for the domain
@@ -19,8 +23,8 @@ impl<F: PrimeField> std::fmt::Display for Domain<F> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
             f,
-            "Domain: size = {}, generator = {}, group_gen_inverse = {}",
-            self.size, self.generator, self.group_gen_inverse
+            "Domain: size = {}, generator = {}, group_gen_inverse = {}, offset = {}",
+            self.size, self.generator, self.group_gen_inverse, self.offset
         )
     }
 }
@@ -36,6 +40,59 @@ impl<F: PrimeField> Domain<F> {
         } as u64;
 
         let generator = F::get_root_of_unity(size).unwrap();
+        Self::from_generator(size, generator)
+    }
+
+    /// The smallest domain of size 2^k * 3^m with at least `num_of_coeffs` elements
+    /// that the field supports, which wastes less than padding to a power of two.
+    pub fn new_mixed_radix(num_of_coeffs: usize) -> Self {
+        let num_of_coeffs = num_of_coeffs.max(1) as u64;
+
+        let mut best: Option<u64> = None;
+        let mut power_of_three = 1u64;
+        while power_of_three < num_of_coeffs.saturating_mul(3) {
+            let mut size = power_of_three;
+            while size < num_of_coeffs {
+                size *= 2;
+            }
+            if best.is_none_or(|best| size < best) && Self::supports_size(size) {
+                best = Some(size);
+            }
+            power_of_three *= 3;
+        }
+
+        Self::with_size(best.expect("The field has no subgroup large enough"))
+            .expect("The size should be supported")
+    }
+
+    /// A domain of exactly `size` elements, for size = 2^k * 3^m dividing p - 1
+    pub fn with_size(size: u64) -> Option<Self> {
+        if !Self::supports_size(size) {
+            return None;
+        }
+
+        if size.is_power_of_two() {
+            return Some(Self::from_generator(size, F::get_root_of_unity(size)?));
+        }
+
+        // g^((p - 1) / size) for the multiplicative generator g
+        let modulus: BigUint = F::MODULUS.into();
+        let exponent = (modulus - 1u32) / size;
+        let generator = F::GENERATOR.pow(exponent.to_u64_digits());
+
+        Some(Self::from_generator(size, generator))
+    }
+
+    fn supports_size(size: u64) -> bool {
+        if size == 0 || size_radices(size).is_none() {
+            return false;
+        }
+
+        let modulus: BigUint = F::MODULUS.into();
+        ((modulus - 1u32) % size) == BigUint::from(0u32)
+    }
+
+    fn from_generator(size: u64, generator: F) -> Self {
         let group_gen_inverse = generator.inverse().unwrap();
         let group_size_inverse = F::from(size).inverse().unwrap();
 
@@ -44,19 +101,21 @@ impl<F: PrimeField> Domain<F> {
             generator,
             group_gen_inverse,
             group_size_inverse,
+            offset: F::one(),
+            offset_inverse: F::one(),
         }
     }
 
-    /// This function is used to obtain the coset of the domain
-    pub fn get_coset(&self, offest: usize) -> Self {
-        let generator = self.generator * F::from(offest as u64);
-        let group_gen_inverse = generator.inverse().unwrap();
+    /// This function is used to obtain the coset offset * H of the domain H
+    pub fn get_coset(&self, offset: F) -> Self {
+        let offset_inverse = offset
+            .inverse()
+            .expect("The coset offset should be non zero");
 
         Domain {
-            size: self.size,
-            generator,
-            group_gen_inverse,
-            group_size_inverse: self.group_size_inverse,
+            offset,
+            offset_inverse,
+            ..self.clone()
         }
     }
 
@@ -100,6 +159,14 @@ impl<F: PrimeField> Domain<F> {
         roots
     }
 
+    /// The elements offset * omega^i of the domain, the roots of unity when it is not a coset
+    pub fn elements(&self) -> Vec<F> {
+        self.get_roots_of_unity()
+            .into_iter()
+            .map(|root| root * self.offset)
+            .collect()
+    }
+
     /// This function is used to get the root of unity
     pub fn get_root_of_unity(&self) -> F {
         self.generator
@@ -117,19 +184,103 @@ impl<F: PrimeField> Domain<F> {
         evals
     }
 
+    /// Evaluations over a coset offset * H, which is the FFT of the coefficients scaled by offset^i
+    pub fn coset_fft(&self, coeffs: &[F], offset: F) -> Vec<F> {
+        let mut coeffs = coeffs.to_vec();
+        self.get_coset(offset).fft_internal(&mut coeffs);
+        coeffs
+    }
+
+    pub fn coset_ifft(&self, evals: &[F], offset: F) -> Vec<F> {
+        let mut evals = evals.to_vec();
+        self.get_coset(offset).ifft_internal(&mut evals);
+        evals
+    }
+
     pub fn fft_internal(&self, coeffs: &mut Vec<F>) {
         coeffs.resize(self.size as usize, F::zero());
-        serial_fft(coeffs, self.generator, self.size.trailing_zeros());
+        distribute_powers(coeffs, self.offset);
+
+        if self.size.is_power_of_two() {
+            serial_fft(coeffs, self.generator, self.size.trailing_zeros());
+        } else {
+            *coeffs = mixed_radix_fft(coeffs, self.generator, &self.radices());
+        }
     }
 
     pub fn ifft_internal(&self, evals: &mut Vec<F>) {
         evals.resize(self.size as usize, F::zero());
-        serial_fft(evals, self.group_gen_inverse, self.size.trailing_zeros());
+        if self.size.is_power_of_two() {
+            serial_fft(evals, self.group_gen_inverse, self.size.trailing_zeros());
+        } else {
+            *evals = mixed_radix_fft(evals, self.group_gen_inverse, &self.radices());
+        }
 
         // scaling down the resulting coefficients
         evals
             .iter_mut()
             .for_each(|eval| *eval *= self.group_size_inverse); //TODO: This can be parallelized!
+        distribute_powers(evals, self.offset_inverse);
+    }
+
+    /// In place FFT without the bit reversal: the evaluations come out in bit-reversed order.
+    /// Pointwise operations don't care about the order, so a product of two polynomials
+    /// can go through `fft_bit_reversed` and `ifft_bit_reversed` without any permutation.
+    pub fn fft_bit_reversed(&self, coeffs: &mut Vec<F>) {
+        assert!(
+            self.size.is_power_of_two(),
+            "Bit-reversed FFTs need a power of two domain"
+        );
+
+        coeffs.resize(self.size as usize, F::zero());
+        distribute_powers(coeffs, self.offset);
+        serial_fft_dif(coeffs, self.generator, self.size.trailing_zeros());
+    }
+
+    /// In place inverse FFT of evaluations given in bit-reversed order
+    pub fn ifft_bit_reversed(&self, evals: &mut Vec<F>) {
+        assert!(
+            self.size.is_power_of_two(),
+            "Bit-reversed FFTs need a power of two domain"
+        );
+
+        evals.resize(self.size as usize, F::zero());
+        serial_fft_dit(evals, self.group_gen_inverse, self.size.trailing_zeros());
+        evals
+            .iter_mut()
+            .for_each(|eval| *eval *= self.group_size_inverse);
+        distribute_powers(evals, self.offset_inverse);
+    }
+
+    /// Z(tau) = tau^n - offset^n, the polynomial vanishing on the whole domain
+    pub fn evaluate_vanishing_polynomial(&self, tau: F) -> F {
+        tau.pow([self.size]) - self.offset.pow([self.size])
+    }
+
+    /// L_i(tau) for every element h_i of the domain, with a single inversion.
+    ///
+    /// Z'(h_i) = n h_i^(n - 1) = n offset^n / h_i, so
+    /// L_i(tau) = Z(tau) h_i / (n offset^n (tau - h_i))
+    pub fn evaluate_all_lagrange_coefficients(&self, tau: F) -> Vec<F> {
+        let elements = self.elements();
+        let vanishing = self.evaluate_vanishing_polynomial(tau);
+
+        if vanishing.is_zero() {
+            return elements
+                .iter()
+                .map(|element| if *element == tau { F::one() } else { F::zero() })
+                .collect();
+        }
+
+        let mut denominators: Vec<F> = elements.iter().map(|element| tau - element).collect();
+        batch_inversion(&mut denominators);
+
+        let scale = vanishing * self.group_size_inverse * self.offset_inverse.pow([self.size]);
+        elements
+            .iter()
+            .zip(denominators.iter())
+            .map(|(element, denominator)| scale * element * denominator)
+            .collect()
     }
 
     pub fn size(&self) -> u64 {
@@ -143,11 +294,53 @@ impl<F: PrimeField> Domain<F> {
     pub fn group_gen_inverse(&self) -> F {
         self.group_gen_inverse
     }
+
+    pub fn offset(&self) -> F {
+        self.offset
+    }
+
+    // radix 4 stages for the power of two part, one radix 2 stage if it is an odd power,
+    // then radix 3 stages
+    fn radices(&self) -> Vec<usize> {
+        size_radices(self.size).expect("The domain size should be 2^k * 3^m")
+    }
+}
+
+fn size_radices(mut size: u64) -> Option<Vec<usize>> {
+    let mut radices = Vec::new();
+    while size.is_multiple_of(4) {
+        radices.push(4);
+        size /= 4;
+    }
+    if size.is_multiple_of(2) {
+        radices.push(2);
+        size /= 2;
+    }
+    while size.is_multiple_of(3) {
+        radices.push(3);
+        size /= 3;
+    }
+
+    (size == 1).then_some(radices)
+}
+
+// values[i] *= g^i
+fn distribute_powers<F: PrimeField>(values: &mut [F], g: F) {
+    if g.is_one() {
+        return;
+    }
+
+    let mut power = F::one();
+    for value in values.iter_mut() {
+        *value *= power;
+        power *= g;
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ark_ff::{Field, One, Zero};
     use ark_test_curves::bls12_381::Fr;
 
     #[test]
@@ -167,4 +360,186 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_domain_over_small_prime_field() {
+        use field::params::fft_friendly_prime;
+        use field::small_prime_field::SmallPrimeField;
+
+        // smallest prime supporting FFTs of size up to 2^6
+        const P: u64 = match fft_friendly_prime(6, 0) {
+            Some(p) => p,
+            None => panic!("No FFT friendly prime"),
+        };
+        type F = SmallPrimeField<P>;
+
+        let domain = Domain::<F>::new(40);
+        assert_eq!(domain.size(), 64);
+        assert_eq!(domain.generator().pow([64]), F::one());
+        assert_ne!(domain.generator().pow([32]), F::one());
+
+        let coeffs: Vec<F> = (0..40u64).map(|i| F::from(i * i + 1)).collect();
+        let evals = domain.fft(&coeffs);
+        let roots = domain.get_roots_of_unity();
+        for (eval, root) in evals.iter().zip(roots.iter()) {
+            let expected = coeffs
+                .iter()
+                .rev()
+                .fold(F::zero(), |acc, coeff| acc * root + coeff);
+            assert_eq!(*eval, expected);
+        }
+
+        let mut recovered = domain.ifft(&evals);
+        recovered.truncate(40);
+        assert_eq!(recovered, coeffs);
+    }
+
+    // Horner evaluation at every point
+    fn naive_evaluations<F: PrimeField>(coeffs: &[F], points: &[F]) -> Vec<F> {
+        points
+            .iter()
+            .map(|point| {
+                coeffs
+                    .iter()
+                    .rev()
+                    .fold(F::zero(), |acc, coeff| acc * point + coeff)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_coset_fft() {
+        let domain = Domain::<Fr>::new(16);
+        let offset = Fr::from(7u64);
+        let coset = domain.get_coset(offset);
+        assert_eq!(coset.generator(), domain.generator());
+
+        let coeffs: Vec<Fr> = (0..16u64).map(|i| Fr::from(i * i + 3)).collect();
+        let evals = domain.coset_fft(&coeffs, offset);
+        assert_eq!(evals, coset.fft(&coeffs));
+        assert_eq!(evals, naive_evaluations(&coeffs, &coset.elements()));
+        assert_eq!(domain.coset_ifft(&evals, offset), coeffs);
+
+        // a coset is disjoint from the subgroup
+        assert!(coset
+            .elements()
+            .iter()
+            .all(|element| !domain.elements().contains(element)));
+    }
+
+    #[test]
+    fn test_mixed_radix_domain() {
+        use field::small_prime_field::SmallPrimeField;
+
+        // 1297 - 1 = 2^4 * 3^4
+        type F = SmallPrimeField<1297>;
+
+        assert_eq!(Domain::<F>::new_mixed_radix(10).size(), 12);
+        assert_eq!(Domain::<F>::new_mixed_radix(25).size(), 27);
+        assert_eq!(Domain::<F>::new_mixed_radix(100).size(), 108);
+        assert!(Domain::<F>::with_size(32).is_none());
+        assert!(Domain::<F>::with_size(10).is_none());
+
+        for size in [1, 2, 3, 6, 8, 9, 12, 16, 27, 48, 81, 144] {
+            let domain = Domain::<F>::with_size(size).unwrap();
+            assert_eq!(domain.generator().pow([size]), F::one());
+            assert_eq!(
+                domain
+                    .elements()
+                    .iter()
+                    .collect::<std::collections::HashSet<_>>()
+                    .len(),
+                size as usize
+            );
+
+            let coeffs: Vec<F> = (0..size).map(|i| F::from(i * 5 + 1)).collect();
+            let evals = domain.fft(&coeffs);
+            assert_eq!(evals, naive_evaluations(&coeffs, &domain.elements()));
+            assert_eq!(domain.ifft(&evals), coeffs);
+
+            let coset = domain.get_coset(F::from(5u64));
+            let evals = coset.fft(&coeffs);
+            assert_eq!(evals, naive_evaluations(&coeffs, &coset.elements()));
+            assert_eq!(coset.ifft(&evals), coeffs);
+        }
+
+        // BLS12-381's scalar field has a single factor of 3 in p - 1
+        let domain = Domain::<Fr>::with_size(48).unwrap();
+        let coeffs: Vec<Fr> = (0..48u64).map(Fr::from).collect();
+        assert_eq!(
+            domain.fft(&coeffs),
+            naive_evaluations(&coeffs, &domain.elements())
+        );
+        assert!(Domain::<Fr>::with_size(9).is_none());
+    }
+
+    #[test]
+    fn test_radix_4_matches_radix_2() {
+        let domain = Domain::<Fr>::new(32);
+        let coeffs: Vec<Fr> = (0..32u64).map(|i| Fr::from(i).pow([i])).collect();
+
+        let mut expected = coeffs.clone();
+        serial_fft(&mut expected, domain.generator(), 5);
+        assert_eq!(
+            mixed_radix_fft(&coeffs, domain.generator(), &[4, 4, 2]),
+            expected
+        );
+        assert_eq!(
+            mixed_radix_fft(&coeffs, domain.generator(), &[2, 2, 2, 2, 2]),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_bit_reversed_fft() {
+        use crate::utils::bit_reverse_permutation;
+
+        let domain = Domain::<Fr>::new(16).get_coset(Fr::from(3u64));
+        let coeffs: Vec<Fr> = (0..16u64).map(|i| Fr::from(2 * i + 1)).collect();
+
+        let mut evals = coeffs.clone();
+        domain.fft_bit_reversed(&mut evals);
+        let mut expected = domain.fft(&coeffs);
+        bit_reverse_permutation(&mut expected, 4);
+        assert_eq!(evals, expected);
+
+        domain.ifft_bit_reversed(&mut evals);
+        assert_eq!(evals, coeffs);
+
+        // a product never leaves the bit-reversed order
+        let mut a = vec![Fr::from(1u64), Fr::from(2u64)];
+        let mut b = vec![Fr::from(3u64), Fr::from(4u64), Fr::from(5u64)];
+        domain.fft_bit_reversed(&mut a);
+        domain.fft_bit_reversed(&mut b);
+        let mut product: Vec<Fr> = a.iter().zip(b.iter()).map(|(a, b)| *a * b).collect();
+        domain.ifft_bit_reversed(&mut product);
+        product.truncate(4);
+        assert_eq!(product, [3u64, 10, 13, 10].map(Fr::from).to_vec());
+    }
+
+    #[test]
+    fn test_vanishing_polynomial_and_lagrange_coefficients() {
+        let domain = Domain::<Fr>::new(8);
+        for domain in [domain.clone(), domain.get_coset(Fr::from(5u64))] {
+            for element in domain.elements() {
+                assert!(domain.evaluate_vanishing_polynomial(element).is_zero());
+            }
+
+            // sum of f(h_i) L_i(tau) = f(tau) for deg f < n
+            let coeffs: Vec<Fr> = (0..8u64).map(|i| Fr::from(i + 11)).collect();
+            let evals = domain.fft(&coeffs);
+            let tau = Fr::from(123456789u64);
+            let lagrange = domain.evaluate_all_lagrange_coefficients(tau);
+            let interpolated: Fr = evals.iter().zip(lagrange.iter()).map(|(e, l)| *e * l).sum();
+            assert_eq!(interpolated, naive_evaluations(&coeffs, &[tau])[0]);
+            assert!(!domain.evaluate_vanishing_polynomial(tau).is_zero());
+
+            // inside the domain L_i is an indicator
+            let h_3 = domain.elements()[3];
+            let lagrange = domain.evaluate_all_lagrange_coefficients(h_3);
+            for (i, l) in lagrange.iter().enumerate() {
+                assert_eq!(*l, if i == 3 { Fr::one() } else { Fr::zero() });
+            }
+        }
+    }
 }
@@ -0,0 +1,332 @@
+use crate::{DenseUnivariatePolynomial, UnivariatePolynomialTrait};
+use ark_ff::{BigInteger, PrimeField};
+use rand::thread_rng;
+
+impl<F: PrimeField> DenseUnivariatePolynomial<F> {
+    /// The polynomial divided by its leading coefficient, zero stays zero
+    pub fn monic(&self) -> Self {
+        let poly = self.remove_leading_zeros();
+        match poly.leading_coefficient() {
+            Some(leading) => poly * leading.inverse().unwrap(),
+            None => poly,
+        }
+    }
+
+    /// The monic greatest common divisor, zero when both polynomials are zero
+    pub fn gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.remove_leading_zeros(), other.remove_leading_zeros());
+        while !b.is_zero() {
+            let remainder = remainder(&a, &b);
+            a = b;
+            b = remainder;
+        }
+        a.monic()
+    }
+
+    /// (g, s, t) with g = gcd(a, b) monic and s a + t b = g, by the extended Euclidean algorithm
+    pub fn extended_gcd(a: &Self, b: &Self) -> (Self, Self, Self) {
+        let (mut r0, mut r1) = (a.remove_leading_zeros(), b.remove_leading_zeros());
+        let (mut s0, mut s1) = (Self::new(vec![F::one()]), Self::zero());
+        let (mut t0, mut t1) = (Self::zero(), Self::new(vec![F::one()]));
+
+        while !r1.is_zero() {
+            let (quotient, remainder) = r0.divide_with_q_and_r(&r1).expect("division failed");
+            (r0, r1) = (r1, remainder.remove_leading_zeros());
+            (s0, s1) = (s1.clone(), sub(&s0, &(quotient.clone() * s1)));
+            (t0, t1) = (t1.clone(), sub(&t0, &(quotient * t1)));
+        }
+
+        match r0.leading_coefficient() {
+            Some(leading) => {
+                let leading_inv = leading.inverse().unwrap();
+                (r0 * leading_inv, s0 * leading_inv, t0 * leading_inv)
+            }
+            None => (Self::zero(), Self::zero(), Self::zero()),
+        }
+    }
+
+    /// self^exponent mod modulus, for a little-endian limb exponent
+    pub fn pow_mod(&self, exponent: &[u64], modulus: &Self) -> Self {
+        let mut result = remainder(&Self::new(vec![F::one()]), modulus);
+        let base = remainder(self, modulus);
+
+        for i in (0..exponent.len() * 64).rev() {
+            result = remainder(&(result.clone() * result), modulus);
+            if (exponent[i / 64] >> (i % 64)) & 1 == 1 {
+                result = remainder(&(result * base.clone()), modulus);
+            }
+        }
+        result
+    }
+
+    /// The square-free factorisation f = c * prod of a_i^i, returned as the monic, pairwise
+    /// coprime, non constant a_i with their multiplicity i.
+    ///
+    /// Musser's algorithm, extended to characteristic p: when f' = 0, f is g(x^p) = g(x)^p
+    /// since the frobenius is the identity on F_p, and g is factored recursively.
+    pub fn square_free_factorization(&self) -> Vec<(Self, usize)> {
+        let f = self.monic();
+        if f.is_zero() || f.degree() == 0 {
+            return vec![];
+        }
+
+        let derivative = f.derivative().remove_leading_zeros();
+        if derivative.is_zero() {
+            return f
+                .pth_root()
+                .square_free_factorization()
+                .into_iter()
+                .map(|(factor, multiplicity)| (factor, multiplicity * characteristic::<F>()))
+                .collect();
+        }
+
+        let mut factors = vec![];
+        let c = f.gcd(&derivative);
+        let mut w = exact_division(&f, &c);
+        let mut c = c;
+        let mut multiplicity = 1;
+
+        // w is the product of the factors with multiplicity at least `multiplicity`
+        // that are not multiples of p
+        while w.degree() > 0 {
+            let y = w.gcd(&c);
+            let factor = exact_division(&w, &y);
+            if factor.degree() > 0 {
+                factors.push((factor, multiplicity));
+            }
+
+            w = y;
+            c = exact_division(&c, &w);
+            multiplicity += 1;
+        }
+
+        // what is left only has factors of multiplicity a multiple of p
+        if c.degree() > 0 {
+            let p = characteristic::<F>();
+            factors.extend(
+                c.pth_root()
+                    .square_free_factorization()
+                    .into_iter()
+                    .map(|(factor, multiplicity)| (factor, multiplicity * p)),
+            );
+        }
+
+        factors
+    }
+
+    /// The distinct roots in F, sorted, `None` for the zero polynomial which vanishes everywhere.
+    ///
+    /// gcd(f, x^p - x) is the product of (x - r) over the roots r, which Cantor–Zassenhaus
+    /// splits with gcd(g, (x + a)^((p - 1) / 2) - 1) for random a: every root r
+    /// goes to the left side exactly when r + a is a non zero square, half of the time.
+    pub fn roots(&self) -> Option<Vec<F>> {
+        let f = self.monic();
+        if f.is_zero() {
+            return None;
+        }
+        if f.degree() == 0 {
+            return Some(vec![]);
+        }
+
+        // x^p - x mod f
+        let x = Self::new(vec![F::zero(), F::one()]);
+        let x_p = x.pow_mod(F::MODULUS.as_ref(), &f);
+        let linear_part = f.gcd(&sub(&x_p, &x));
+
+        let mut roots = vec![];
+        split_linear_factors(&linear_part, &mut roots);
+        roots.sort();
+        Some(roots)
+    }
+
+    // f = g(x^p) -> g, the coefficients of f being non zero only at multiples of p
+    fn pth_root(&self) -> Self {
+        let p = characteristic::<F>();
+        let coefficients = self.coefficients.iter().step_by(p).copied().collect();
+        Self::new(coefficients)
+    }
+}
+
+// g is monic and a product of distinct linear factors
+fn split_linear_factors<F: PrimeField>(g: &DenseUnivariatePolynomial<F>, roots: &mut Vec<F>) {
+    match g.degree() {
+        0 => {}
+        1 => roots.push(-g.coefficients[0]),
+        _ if F::MODULUS.as_ref() == [2] => {
+            // only 0 and 1 remain, and both are roots
+            roots.extend([F::zero(), F::one()]);
+        }
+        _ => {
+            let mut half = F::MODULUS;
+            half.div2();
+            let one = DenseUnivariatePolynomial::new(vec![F::one()]);
+            let mut rng = thread_rng();
+
+            loop {
+                let shift = DenseUnivariatePolynomial::new(vec![F::rand(&mut rng), F::one()]);
+                let power = shift.pow_mod(half.as_ref(), g);
+                let factor = g.gcd(&sub(&power, &one));
+
+                if factor.degree() > 0 && factor.degree() < g.degree() {
+                    split_linear_factors(&factor, roots);
+                    split_linear_factors(&exact_division(g, &factor), roots);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+// p as a usize, only called when a polynomial has a p-th power, so p is at most its degree
+fn characteristic<F: PrimeField>() -> usize {
+    F::MODULUS.as_ref()[0] as usize
+}
+
+fn remainder<F: PrimeField>(
+    a: &DenseUnivariatePolynomial<F>,
+    b: &DenseUnivariatePolynomial<F>,
+) -> DenseUnivariatePolynomial<F> {
+    a.divide_with_q_and_r(b)
+        .expect("division failed")
+        .1
+        .remove_leading_zeros()
+}
+
+fn exact_division<F: PrimeField>(
+    a: &DenseUnivariatePolynomial<F>,
+    b: &DenseUnivariatePolynomial<F>,
+) -> DenseUnivariatePolynomial<F> {
+    a.divide_with_q_and_r(b)
+        .expect("division failed")
+        .0
+        .remove_leading_zeros()
+}
+
+fn sub<F: PrimeField>(
+    a: &DenseUnivariatePolynomial<F>,
+    b: &DenseUnivariatePolynomial<F>,
+) -> DenseUnivariatePolynomial<F> {
+    let len = a.coefficients.len().max(b.coefficients.len());
+    let coefficients = (0..len)
+        .map(|i| {
+            *a.coefficients.get(i).unwrap_or(&F::zero())
+                - b.coefficients.get(i).unwrap_or(&F::zero())
+        })
+        .collect();
+
+    DenseUnivariatePolynomial::new(coefficients).remove_leading_zeros()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::generate_random_numbers;
+    use ark_test_curves::bls12_381::Fr;
+    use field::small_prime_field::SmallPrimeField;
+
+    type F17 = SmallPrimeField<17>;
+
+    // prod of (x - r) over the roots
+    fn from_roots<F: PrimeField>(roots: &[F]) -> DenseUnivariatePolynomial<F> {
+        roots.iter().fold(
+            DenseUnivariatePolynomial::new(vec![F::one()]),
+            |acc, root| acc * DenseUnivariatePolynomial::new(vec![-*root, F::one()]),
+        )
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        let common = from_roots(&[Fr::from(3u64), Fr::from(5u64)]);
+        let a = common.clone() * from_roots(&[Fr::from(7u64), Fr::from(11u64)]);
+        let b = common.clone() * DenseUnivariatePolynomial::new(generate_random_numbers(5));
+
+        let (g, s, t) = DenseUnivariatePolynomial::extended_gcd(&a, &b);
+        assert_eq!(g, common);
+        assert_eq!(a.gcd(&b), common);
+
+        // s a + t b = g
+        let point = Fr::from(1234567u64);
+        assert_eq!(
+            s.evaluate(point) * a.evaluate(point) + t.evaluate(point) * b.evaluate(point),
+            g.evaluate(point)
+        );
+        assert_eq!((s * a + t * b).remove_leading_zeros(), g);
+
+        // coprime polynomials
+        let one = DenseUnivariatePolynomial::new(vec![Fr::from(1u64)]);
+        assert_eq!(
+            from_roots(&[Fr::from(1u64)]).gcd(&from_roots(&[Fr::from(2u64)])),
+            one
+        );
+    }
+
+    #[test]
+    fn test_square_free_factorization() {
+        // (x - 1)(x - 2)^2 (x - 3)^3
+        let (a1, a2, a3) = (
+            from_roots(&[Fr::from(1u64)]),
+            from_roots(&[Fr::from(2u64)]),
+            from_roots(&[Fr::from(3u64)]),
+        );
+        let f = a1.clone() * a2.clone() * a2.clone() * a3.clone() * a3.clone() * a3.clone();
+        assert_eq!(
+            (f * Fr::from(5u64)).square_free_factorization(),
+            vec![(a1, 1), (a2, 2), (a3, 3)]
+        );
+
+        // over F_17, (x - 1)^17 (x - 2) = (x^17 - 1)(x - 2) has a derivative without (x - 1)^16
+        let (b1, b2) = (
+            from_roots(&[F17::from(1u64)]),
+            from_roots(&[F17::from(2u64)]),
+        );
+        let f = (0..17).fold(b2.clone(), |acc, _| acc * b1.clone());
+        assert_eq!(
+            f.square_free_factorization(),
+            vec![(b2, 1), (b1.clone(), 17)]
+        );
+
+        // and x^17 - 1 = (x - 1)^17 has a zero derivative
+        let f = (0..17).fold(
+            DenseUnivariatePolynomial::new(vec![F17::from(1u64)]),
+            |acc, _| acc * b1.clone(),
+        );
+        assert_eq!(f.square_free_factorization(), vec![(b1, 17)]);
+    }
+
+    #[test]
+    fn test_roots() {
+        let mut roots: Vec<Fr> = generate_random_numbers(20);
+        // repeated roots are only listed once
+        let f = from_roots(&roots) * from_roots(&roots[..3]);
+        roots.sort();
+        assert_eq!(f.roots(), Some(roots));
+
+        // x^2 + 1 has no roots as -1 is not a square mod 19
+        type F19 = SmallPrimeField<19>;
+        let irreducible =
+            DenseUnivariatePolynomial::new(vec![F19::from(1u64), F19::from(0u64), F19::from(1u64)]);
+        let f = irreducible * from_roots(&[F19::from(4u64), F19::from(0u64), F19::from(4u64)]);
+        assert_eq!(f.roots(), Some(vec![F19::from(0u64), F19::from(4u64)]));
+
+        // x^17 - x vanishes on all of F_17
+        let mut coefficients = vec![F17::from(0u64); 18];
+        coefficients[1] = -F17::from(1u64);
+        coefficients[17] = F17::from(1u64);
+        let all: Vec<F17> = (0..17u64).map(F17::from).collect();
+        assert_eq!(
+            DenseUnivariatePolynomial::new(coefficients).roots(),
+            Some(all)
+        );
+
+        // constants have none, and the zero polynomial has every element as a root
+        assert_eq!(
+            DenseUnivariatePolynomial::new(vec![Fr::from(3u64)]).roots(),
+            Some(vec![])
+        );
+        assert_eq!(DenseUnivariatePolynomial::<Fr>::new(vec![]).roots(), None);
+        assert_eq!(
+            DenseUnivariatePolynomial::new(vec![Fr::from(0u64); 3]).roots(),
+            None
+        );
+    }
+}
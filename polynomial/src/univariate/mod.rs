@@ -1,4 +1,7 @@
 pub mod dense_univariate;
 pub mod domain;
 pub mod evaluation;
+pub mod factorization;
+pub mod reed_solomon;
 pub mod sparse_univariate;
+pub mod subproduct_tree;
@@ -0,0 +1,270 @@
+use crate::{
+    univariate::{domain::Domain, subproduct_tree::SubproductTree},
+    DenseUnivariatePolynomial, UnivariatePolynomialTrait,
+};
+use ark_ff::PrimeField;
+
+/// The Reed–Solomon code RS[n, k]: a message of k field elements is read as the
+/// coefficients of a polynomial f of degree < k, and its codeword is f evaluated over
+/// a domain of n = k * blowup roots of unity, computed with one FFT.
+///
+/// Any k symbols determine f, so up to n - k erasures can be recovered, and any
+/// combination of e errors and s erasures with 2e + s <= n - k can be corrected.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReedSolomon<F: PrimeField> {
+    /// This is the number of message symbols k
+    pub(crate) message_len: usize,
+    /// This is the evaluation domain, of size n
+    pub(crate) domain: Domain<F>,
+}
+
+impl<F: PrimeField> ReedSolomon<F> {
+    /// A code for messages of `message_len` symbols with rate 1 / blowup,
+    /// the codeword length being rounded up to a power of two
+    pub fn new(message_len: usize, blowup: usize) -> Self {
+        assert!(message_len > 0, "The message should not be empty");
+        assert!(blowup > 0, "The blowup factor should be at least one");
+
+        ReedSolomon {
+            message_len,
+            domain: Domain::new(message_len * blowup),
+        }
+    }
+
+    /// The same code over the coset offset * H, as FRI commits over
+    pub fn with_offset(&self, offset: F) -> Self {
+        ReedSolomon {
+            message_len: self.message_len,
+            domain: self.domain.get_coset(offset),
+        }
+    }
+
+    pub fn message_len(&self) -> usize {
+        self.message_len
+    }
+
+    pub fn codeword_len(&self) -> usize {
+        self.domain.size() as usize
+    }
+
+    pub fn domain(&self) -> &Domain<F> {
+        &self.domain
+    }
+
+    /// The number of errors that can always be corrected, (n - k) / 2
+    pub fn max_errors(&self) -> usize {
+        (self.codeword_len() - self.message_len) / 2
+    }
+
+    /// The evaluations of the message polynomial over the domain
+    pub fn encode(&self, message: &[F]) -> Result<Vec<F>, &'static str> {
+        if message.len() > self.message_len {
+            return Err("The message is longer than the code dimension");
+        }
+
+        Ok(self.domain.fft(&message.to_vec()))
+    }
+
+    /// A word is in the code when its interpolant has degree < k
+    pub fn is_codeword(&self, word: &[F]) -> bool {
+        if word.len() != self.codeword_len() {
+            return false;
+        }
+
+        let coefficients = self.domain.ifft(&word.to_vec());
+        coefficients[self.message_len..]
+            .iter()
+            .all(|coeff| coeff.is_zero())
+    }
+
+    /// The message from a codeword with erased (`None`) but no wrong symbols.
+    ///
+    /// The message is interpolated from the first k known symbols and the others
+    /// are checked against it.
+    pub fn decode_erasures(&self, received: &[Option<F>]) -> Result<Vec<F>, &'static str> {
+        let (xs, ys) = self.known_symbols(received)?;
+        if xs.len() < self.message_len {
+            return Err("Too many erasures");
+        }
+
+        let poly = DenseUnivariatePolynomial::interpolate(
+            ys[..self.message_len].to_vec(),
+            xs[..self.message_len].to_vec(),
+        );
+        if poly.evaluate_many(&xs[self.message_len..]) != ys[self.message_len..] {
+            return Err("The known symbols are not consistent with a codeword");
+        }
+
+        Ok(self.message_from(poly))
+    }
+
+    /// The message from a codeword with errors and erasures, by Gao's algorithm.
+    ///
+    /// With m known symbols (x_i, y_i), g0 = prod of (x - x_i) and g1 the interpolant of
+    /// the received symbols, the extended Euclidean algorithm on (g0, g1) is stopped at
+    /// the first remainder g = u g0 + v g1 of degree < (m + k) / 2. When 2e < m - k,
+    /// v is the error locator up to a constant and f = g / v.
+    pub fn decode(&self, received: &[Option<F>]) -> Result<Vec<F>, &'static str> {
+        let (xs, ys) = self.known_symbols(received)?;
+        let m = xs.len();
+        if m < self.message_len {
+            return Err("Too many erasures");
+        }
+
+        let g0 = SubproductTree::new(&xs).product;
+        let g1 = DenseUnivariatePolynomial::interpolate(ys, xs).remove_leading_zeros();
+
+        let (mut r0, mut r1) = (g0, g1);
+        let (mut v0, mut v1) = (
+            DenseUnivariatePolynomial::zero(),
+            DenseUnivariatePolynomial::new(vec![F::one()]),
+        );
+        while !r1.is_zero() && 2 * r1.degree() >= m + self.message_len {
+            let (quotient, remainder) = r0.divide_with_q_and_r(&r1).expect("division failed");
+            let v2 = (v0 - quotient * v1.clone()).remove_leading_zeros();
+
+            (r0, r1) = (r1, remainder.remove_leading_zeros());
+            (v0, v1) = (v1, v2);
+        }
+
+        let (f, remainder) = r1.divide_with_q_and_r(&v1).expect("division failed");
+        let f = f.remove_leading_zeros();
+        if !remainder.remove_leading_zeros().is_zero()
+            || (!f.is_zero() && f.degree() >= self.message_len)
+        {
+            return Err("Too many errors");
+        }
+
+        Ok(self.message_from(f))
+    }
+
+    // the domain points and values of the symbols that were not erased
+    fn known_symbols(&self, received: &[Option<F>]) -> Result<(Vec<F>, Vec<F>), &'static str> {
+        if received.len() != self.codeword_len() {
+            return Err("The received word does not have the length of a codeword");
+        }
+
+        Ok(self
+            .domain
+            .elements()
+            .into_iter()
+            .zip(received.iter())
+            .filter_map(|(x, y)| y.map(|y| (x, y)))
+            .unzip())
+    }
+
+    fn message_from(&self, poly: DenseUnivariatePolynomial<F>) -> Vec<F> {
+        let mut message = poly.coefficients;
+        message.resize(self.message_len, F::zero());
+        message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::generate_random_numbers;
+    use ark_test_curves::bls12_381::Fr;
+
+    fn received(codeword: &[Fr]) -> Vec<Option<Fr>> {
+        codeword.iter().copied().map(Some).collect()
+    }
+
+    #[test]
+    fn test_encode() {
+        let code = ReedSolomon::<Fr>::new(8, 4);
+        assert_eq!(code.codeword_len(), 32);
+        assert_eq!(code.max_errors(), 12);
+
+        let message: Vec<Fr> = generate_random_numbers(8);
+        let codeword = code.encode(&message).unwrap();
+        assert!(code.is_codeword(&codeword));
+
+        // symbol i is the message polynomial at ω^i
+        let poly = DenseUnivariatePolynomial::new(message.clone());
+        for (element, symbol) in code.domain().elements().iter().zip(codeword.iter()) {
+            assert_eq!(poly.evaluate(*element), *symbol);
+        }
+
+        let mut corrupted = codeword.clone();
+        corrupted[5] += Fr::from(1u64);
+        assert!(!code.is_codeword(&corrupted));
+
+        assert!(code.encode(&generate_random_numbers(9)).is_err());
+    }
+
+    #[test]
+    fn test_decode_erasures() {
+        let code = ReedSolomon::<Fr>::new(16, 4);
+        let message: Vec<Fr> = generate_random_numbers(16);
+        let codeword = code.encode(&message).unwrap();
+
+        // n - k = 48 erasures, only every fourth symbol survives
+        let mut word = received(&codeword);
+        for (i, symbol) in word.iter_mut().enumerate() {
+            if i % 4 != 0 {
+                *symbol = None;
+            }
+        }
+        assert_eq!(code.decode_erasures(&word).unwrap(), message);
+        assert_eq!(code.decode(&word).unwrap(), message);
+
+        // one more is too many
+        word[0] = None;
+        assert!(code.decode_erasures(&word).is_err());
+
+        // an error among the known symbols is detected
+        let mut word = received(&codeword);
+        word[40] = Some(codeword[40] + Fr::from(1u64));
+        assert!(code.decode_erasures(&word).is_err());
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let code = ReedSolomon::<Fr>::new(16, 4).with_offset(Fr::from(7u64));
+        let message: Vec<Fr> = generate_random_numbers(16);
+        let codeword = code.encode(&message).unwrap();
+        assert_eq!(code.decode(&received(&codeword)).unwrap(), message);
+
+        // the maximum number of errors, spread over the word
+        let mut word = received(&codeword);
+        for i in 0..code.max_errors() {
+            let position = (i * 37) % code.codeword_len();
+            word[position] = Some(codeword[position] + Fr::from(i as u64 + 1));
+        }
+        assert_eq!(code.decode(&word).unwrap(), message);
+
+        // errors and erasures with 2e + s = n - k
+        let mut word = received(&codeword);
+        for i in 0..10 {
+            word[3 * i] = Some(codeword[3 * i] - Fr::from(2u64));
+        }
+        for symbol in word[36..64].iter_mut() {
+            *symbol = None;
+        }
+        assert_eq!(code.decode(&word).unwrap(), message);
+
+        // far beyond the decoding radius
+        let mut word = received(&codeword);
+        for symbol in word[..40].iter_mut() {
+            *symbol = Some(Fr::from(3u64));
+        }
+        assert_ne!(code.decode(&word), Ok(message));
+    }
+
+    #[test]
+    fn test_short_messages() {
+        // zero and constant messages, and a message shorter than k
+        let code = ReedSolomon::<Fr>::new(4, 2);
+        for message in [vec![], vec![Fr::from(0u64)], vec![Fr::from(9u64)]] {
+            let codeword = code.encode(&message).unwrap();
+            let mut expected = message.clone();
+            expected.resize(4, Fr::from(0u64));
+
+            let mut word = received(&codeword);
+            word[1] = Some(Fr::from(5u64));
+            word[6] = None;
+            assert_eq!(code.decode(&word).unwrap(), expected);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     interface::UnivariatePolynomialTrait,
+    univariate::{dense_univariate::FAST_INTERPOLATION_THRESHOLD, subproduct_tree::SubproductTree},
     utils::{lagrange_basis, prime_field_to_usize},
 };
 use ark_ff::{BigInteger, PrimeField};
@@ -40,12 +41,17 @@ impl<F: PrimeField> SparseUnivariatePolynomial<F> {
     pub fn interpolation(points: &[(F, F)]) -> SparseUnivariatePolynomial<F> {
         let mut result: Vec<F> = vec![F::zero(); points.len()];
 
-        for (i, &(_, y_i)) in points.iter().enumerate() {
-            let l_i: Vec<F> = lagrange_basis(points, i);
-            let l_i: Vec<F> = l_i.into_iter().map(|coeff| coeff * y_i).collect();
+        if points.len() > FAST_INTERPOLATION_THRESHOLD {
+            let (xs, ys): (Vec<F>, Vec<F>) = points.iter().copied().unzip();
+            result = SubproductTree::new(&xs).interpolate(&ys).coefficients;
+        } else {
+            for (i, &(_, y_i)) in points.iter().enumerate() {
+                let l_i: Vec<F> = lagrange_basis(points, i);
+                let l_i: Vec<F> = l_i.into_iter().map(|coeff| coeff * y_i).collect();
 
-            for (k, &coeff) in l_i.iter().enumerate() {
-                result[k] += coeff;
+                for (k, &coeff) in l_i.iter().enumerate() {
+                    result[k] += coeff;
+                }
             }
         }
 
@@ -457,4 +463,24 @@ mod tests {
         assert_eq!(degree, 4);
         // println!("{}", Fq::summary());
     }
+
+    #[test]
+    fn test_fast_interpolation() {
+        // enough points to go through the subproduct tree
+        let coeffs: Vec<Fq> = (0..100u64).map(|i| Fq::from(i * i + 7)).collect();
+        let points: Vec<(Fq, Fq)> = (0..100u64)
+            .map(|x| {
+                let x = Fq::from(3 * x + 1);
+                let y = coeffs
+                    .iter()
+                    .rev()
+                    .fold(Fq::from(0), |acc, coeff| acc * x + coeff);
+                (x, y)
+            })
+            .collect();
+
+        let interpolation = SparseUnivariatePolynomial::interpolation(&points);
+        assert_eq!(interpolation.from_coefficients(), coeffs);
+        assert_eq!(interpolation.degree(), 99);
+    }
 }
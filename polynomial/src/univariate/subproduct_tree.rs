@@ -0,0 +1,185 @@
+use crate::{DenseUnivariatePolynomial, UnivariatePolynomialTrait};
+use ark_ff::PrimeField;
+
+// Below this many points a node evaluates with Horner's rule instead of splitting further
+const LEAF_SIZE: usize = 8;
+
+/// The subproduct tree of points u_0, ..., u_(n-1): every node holds the product of
+/// (x - u_i) over the points below it, the root being the vanishing polynomial of all of them.
+///
+/// Reducing a polynomial modulo the children of each node going down the tree evaluates
+/// it at every point, and combining weighted children going up interpolates, both in
+/// O(M(n) log n) = O(n log^2 n) with NTT multiplication.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubproductTree<F: PrimeField> {
+    /// prod (x - u_i) over the points of this node
+    pub product: DenseUnivariatePolynomial<F>,
+    points: Vec<F>,
+    children: Option<Box<(SubproductTree<F>, SubproductTree<F>)>>,
+}
+
+impl<F: PrimeField> SubproductTree<F> {
+    pub fn new(points: &[F]) -> Self {
+        assert!(!points.is_empty(), "The tree needs at least one point");
+
+        if points.len() <= LEAF_SIZE {
+            let product = points.iter().fold(
+                DenseUnivariatePolynomial::new(vec![F::one()]),
+                |acc, point| acc * DenseUnivariatePolynomial::new(vec![-*point, F::one()]),
+            );
+
+            return SubproductTree {
+                product,
+                points: points.to_vec(),
+                children: None,
+            };
+        }
+
+        let (left, right) = points.split_at(points.len() / 2);
+        let left = SubproductTree::new(left);
+        let right = SubproductTree::new(right);
+
+        SubproductTree {
+            product: left.product.clone() * right.product.clone(),
+            points: points.to_vec(),
+            children: Some(Box::new((left, right))),
+        }
+    }
+
+    pub fn points(&self) -> &[F] {
+        &self.points
+    }
+
+    /// The evaluations of `poly` at every point of the tree, in order
+    pub fn evaluate(&self, poly: &DenseUnivariatePolynomial<F>) -> Vec<F> {
+        let remainder = remainder(poly, &self.product);
+
+        match &self.children {
+            None => self
+                .points
+                .iter()
+                .map(|point| remainder.evaluate(*point))
+                .collect(),
+            Some(children) => {
+                let mut evaluations = children.0.evaluate(&remainder);
+                evaluations.extend(children.1.evaluate(&remainder));
+                evaluations
+            }
+        }
+    }
+
+    /// The polynomial of degree < n through (u_i, values[i]).
+    ///
+    /// With m the root product, the Lagrange basis is L_i = m / ((x - u_i) m'(u_i)), so
+    /// the interpolant is sum of c_i m / (x - u_i) with c_i = values[i] / m'(u_i), which
+    /// `linear_combination` computes bottom up.
+    pub fn interpolate(&self, values: &[F]) -> DenseUnivariatePolynomial<F> {
+        assert_eq!(
+            self.points.len(),
+            values.len(),
+            "The number of points and values should be the same"
+        );
+
+        // m'(u_i) is zero exactly when u_i is repeated
+        let mut weights = self.evaluate(&self.product.derivative());
+        assert!(
+            weights.iter().all(|weight| !weight.is_zero()),
+            "The points should be distinct"
+        );
+        ark_ff::batch_inversion(&mut weights);
+        let weights: Vec<F> = weights
+            .iter()
+            .zip(values.iter())
+            .map(|(weight, value)| *weight * value)
+            .collect();
+
+        let mut result = self.linear_combination(&weights);
+        result.coefficients.resize(self.points.len(), F::zero());
+        result
+    }
+
+    // sum of weights[i] * product / (x - u_i)
+    fn linear_combination(&self, weights: &[F]) -> DenseUnivariatePolynomial<F> {
+        match &self.children {
+            None => {
+                let mut result = DenseUnivariatePolynomial::new(vec![F::zero(); self.points.len()]);
+                for (i, weight) in weights.iter().enumerate() {
+                    let others: Vec<F> = self
+                        .points
+                        .iter()
+                        .enumerate()
+                        .filter(|(j, _)| *j != i)
+                        .map(|(_, point)| *point)
+                        .collect();
+                    let basis = others.iter().fold(
+                        DenseUnivariatePolynomial::new(vec![*weight]),
+                        |acc, point| acc * DenseUnivariatePolynomial::new(vec![-*point, F::one()]),
+                    );
+                    for (k, coeff) in basis.coefficients.iter().enumerate() {
+                        result.coefficients[k] += coeff;
+                    }
+                }
+                result
+            }
+            Some(children) => {
+                let (left, right) = children.as_ref();
+                let (left_weights, right_weights) = weights.split_at(left.points.len());
+
+                left.linear_combination(left_weights) * right.product.clone()
+                    + right.linear_combination(right_weights) * left.product.clone()
+            }
+        }
+    }
+}
+
+fn remainder<F: PrimeField>(
+    poly: &DenseUnivariatePolynomial<F>,
+    divisor: &DenseUnivariatePolynomial<F>,
+) -> DenseUnivariatePolynomial<F> {
+    poly.divide_with_q_and_r(divisor)
+        .expect("division failed")
+        .1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::generate_random_numbers;
+    use ark_test_curves::bls12_381::Fr;
+
+    #[test]
+    fn test_product_vanishes_on_points() {
+        let points: Vec<Fr> = (0..37u64).map(|i| Fr::from(i * i + 1)).collect();
+        let tree = SubproductTree::new(&points);
+
+        assert_eq!(tree.product.degree(), 37);
+        assert_eq!(tree.product.leading_coefficient(), Some(Fr::from(1u64)));
+        for point in points.iter() {
+            assert_eq!(tree.product.evaluate(*point), Fr::from(0u64));
+        }
+    }
+
+    #[test]
+    fn test_evaluate_and_interpolate() {
+        for n in [1, 5, 8, 9, 50, 200] {
+            let points: Vec<Fr> = generate_random_numbers(n);
+            let poly = DenseUnivariatePolynomial::new(generate_random_numbers(n));
+            let tree = SubproductTree::new(&points);
+
+            let evaluations = tree.evaluate(&poly);
+            let expected: Vec<Fr> = points.iter().map(|point| poly.evaluate(*point)).collect();
+            assert_eq!(evaluations, expected);
+
+            assert_eq!(tree.interpolate(&evaluations), poly);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "The points should be distinct")]
+    fn test_interpolate_repeated_points() {
+        let points = [Fr::from(1u64), Fr::from(2u64), Fr::from(1u64)];
+        let tree = SubproductTree::new(&points);
+
+        let _ = tree.interpolate(&[Fr::from(3u64), Fr::from(4u64), Fr::from(5u64)]);
+    }
+}
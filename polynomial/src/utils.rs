@@ -2,12 +2,10 @@ use crate::{
     multilinear::coefficient_form::MultiLinearMonomial,
     univariate::dense_univariate::DenseUnivariatePolynomial, UnivariatePolynomialTrait,
 };
-use ark_ff::{BigInteger, PrimeField, Zero};
+use ark_ff::{BigInteger, PrimeField};
 use num_bigint::BigUint;
-use num_complex::{Complex, Complex64};
 use num_traits::ToPrimitive;
 use rand::thread_rng;
-use std::f64::consts::PI;
 
 pub fn pick_pairs_with_index<F: PrimeField>(
     terms: &Vec<MultiLinearMonomial<F>>,
@@ -156,76 +154,44 @@ pub fn boolean_hypercube<F: PrimeField>(n: usize) -> Vec<Vec<F>> {
     hypercube
 }
 
-pub fn fft(coefficients: &Vec<Complex64>, inverse: bool) -> Vec<Complex64> {
-    // pub fn fft(coefficients: &mut Vec<Complex64>, inverse: bool) {
-    let length_of_coefficients = coefficients.len();
+/// Multilinear coefficients to evaluations over the boolean hypercube, in O(n 2^n).
+/// Entry i is the coefficient of the product of the variables whose bits are set in i,
+/// and pass j adds the x_j = 0 half into the x_j = 1 half.
+pub fn zeta_transform<F: PrimeField>(values: &mut [F]) {
+    assert!(
+        values.len().is_power_of_two(),
+        "The number of values must be a power of 2"
+    );
 
-    if length_of_coefficients <= 1 {
-        return coefficients.to_vec();
-    }
-    // Pe = [P0,P2,...,Pn-2]
-    let poly_even = get_even_indexed_coefficients(&coefficients);
-    // Po = [P1,P3,...,Pn-1]
-    let poly_odd = get_odd_indexed_coefficients(&coefficients);
-
-    // y_e = fft(Pe)
-    let y_e = fft(&poly_even, inverse);
-    // y_o = fft(Po)
-    let y_o = fft(&poly_odd, inverse);
-
-    // nth root of unity => Z^n = 1
-    // 2π/n => e^iθ = cos(θ) + i.sin(θ)
-    // ω = e^(2πi/n)
-    let ω = nth_root_of_unity(length_of_coefficients, inverse);
-
-    // y = [0] * n
-    let mut y = vec![Complex::zero(); length_of_coefficients];
-    let half_len = length_of_coefficients / 2;
-
-    let mut w_i = Complex64::new(1.0, 0.0);
-
-    for i in 0..half_len {
-        if inverse {
-            y[i] = y_e[i] + (w_i * y_o[i]);
-            y[i + half_len] = y_e[i] - (w_i * y_o[i]);
-            y[i] /= 2.0;
-            y[i + half_len] /= 2.0;
-        } else {
-            y[i] = y_e[i] + (w_i * y_o[i]);
-            y[i + half_len] = y_e[i] - (w_i * y_o[i]);
+    let mut stride = 1;
+    while stride < values.len() {
+        for i in 0..values.len() {
+            if i & stride != 0 {
+                let low = values[i ^ stride];
+                values[i] += low;
+            }
         }
-
-        w_i *= ω;
+        stride <<= 1;
     }
-
-    y
-}
-
-fn nth_root_of_unity(n: usize, inverse: bool) -> Complex<f64> {
-    let degree = if inverse {
-        // -2π/n
-        (-2.0 * PI) / (n as f64)
-    } else {
-        // 2π/n
-        (2.0 * PI) / (n as f64)
-    };
-
-    // e^iθ = cos(θ) + i.sin(θ)
-    Complex::new(degree.cos(), degree.sin())
 }
 
-pub fn get_even_indexed_coefficients<T: Clone>(input: &[T]) -> Vec<T> {
-    input[..].iter().step_by(2).cloned().collect()
-}
-
-pub fn get_odd_indexed_coefficients<T: Clone>(input: &[T]) -> Vec<T> {
-    input[1..].iter().step_by(2).cloned().collect()
-}
+/// The inverse of `zeta_transform`, evaluations over the boolean hypercube to multilinear coefficients
+pub fn mobius_transform<F: PrimeField>(values: &mut [F]) {
+    assert!(
+        values.len().is_power_of_two(),
+        "The number of values must be a power of 2"
+    );
 
-pub fn convert_prime_field_to_f64<F: PrimeField>(input: F) -> f64 {
-    let bigint = input.into_bigint();
-    let biguint = BigUint::from_bytes_le(&bigint.to_bytes_le());
-    biguint.to_f64().unwrap()
+    let mut stride = 1;
+    while stride < values.len() {
+        for i in 0..values.len() {
+            if i & stride != 0 {
+                let low = values[i ^ stride];
+                values[i] -= low;
+            }
+        }
+        stride <<= 1;
+    }
 }
 
 pub fn prime_field_to_usize<F: PrimeField>(input: F) -> usize {
@@ -270,29 +236,51 @@ pub fn remove_trailing_and_redundant_zeros<F: PrimeField>(coeff: &Vec<F>) -> Vec
     coefficients
 }
 
-pub fn compute_complex_form<F: PrimeField>(coeffs: &Vec<F>) -> Vec<Complex64> {
-    coeffs
-        .iter()
-        .map(|&x| Complex64::new(convert_prime_field_to_f64(x), 0.0))
-        .collect()
-}
-
 /// this algorithm was gotten from dusk-plonk implemenation
-pub fn serial_fft<F: PrimeField>(list: &mut Vec<F>, w: F, size_log: u32) {
+pub fn serial_fft<F: PrimeField>(list: &mut [F], w: F, size_log: u32) {
     let n = list.len() as u32;
     // this is also a check ensure that the size of the list is a power of 2
     assert_eq!(n, 1 << size_log);
 
-    for k in 0..n {
-        let rk = bitreverse(k, size_log);
-        if k < rk {
-            list.swap(rk as usize, k as usize);
+    bit_reverse_permutation(list, size_log);
+    serial_fft_dit(list, w, size_log);
+}
+
+/// Decimation in frequency (Gentleman-Sande): natural order in, bit-reversed order out
+pub fn serial_fft_dif<F: PrimeField>(list: &mut [F], w: F, size_log: u32) {
+    let n = list.len() as u32;
+    assert_eq!(n, 1 << size_log);
+
+    let mut m = n / 2;
+    for _ in 0..size_log {
+        let w_m = w.pow([(n / (2 * m)) as u64]);
+
+        let mut k = 0;
+        while k < n {
+            let mut w = F::one();
+            for j in 0..m {
+                let u = list[(k + j) as usize];
+                let v = list[(k + j + m) as usize];
+                list[(k + j) as usize] = u + v;
+                list[(k + j + m) as usize] = (u - v) * w;
+                w *= w_m;
+            }
+
+            k += 2 * m;
         }
+
+        m /= 2;
     }
+}
+
+/// Decimation in time (Cooley-Tukey): bit-reversed order in, natural order out
+pub fn serial_fft_dit<F: PrimeField>(list: &mut [F], w: F, size_log: u32) {
+    let n = list.len() as u32;
+    assert_eq!(n, 1 << size_log);
 
     let mut m = 1;
     for _ in 0..size_log {
-        let w_m = w.pow(&[(n / (2 * m)) as u64, 0, 0, 0]);
+        let w_m = w.pow([(n / (2 * m)) as u64]);
 
         let mut k = 0;
         while k < n {
@@ -314,6 +302,85 @@ pub fn serial_fft<F: PrimeField>(list: &mut Vec<F>, w: F, size_log: u32) {
     }
 }
 
+pub fn bit_reverse_permutation<F>(list: &mut [F], size_log: u32) {
+    for k in 0..list.len() as u32 {
+        let rk = bitreverse(k, size_log);
+        if k < rk {
+            list.swap(rk as usize, k as usize);
+        }
+    }
+}
+
+/// Mixed radix Cooley-Tukey over a domain of size radices[0] * radices[1] * ...,
+/// w being a root of unity of exactly that order. Radices 2, 3 and 4 are supported.
+///
+/// The input is split into r decimated sequences x[j], x[j + r], ..., whose transforms
+/// S_j are combined with an r-point DFT: X[k + qm] = sum over j of (w^(jk) S_j[k]) ζ^(jq),
+/// ζ = w^m being a primitive r-th root of unity.
+pub fn mixed_radix_fft<F: PrimeField>(list: &[F], w: F, radices: &[usize]) -> Vec<F> {
+    let n = list.len();
+    assert_eq!(
+        n,
+        radices.iter().product::<usize>(),
+        "The radices should multiply to the size of the list"
+    );
+
+    if n == 1 {
+        return list.to_vec();
+    }
+
+    let r = radices[0];
+    let m = n / r;
+    let w_r = w.pow([r as u64]);
+    let sub_transforms: Vec<Vec<F>> = (0..r)
+        .map(|j| {
+            let decimated: Vec<F> = list[j..].iter().step_by(r).copied().collect();
+            mixed_radix_fft(&decimated, w_r, &radices[1..])
+        })
+        .collect();
+
+    let zeta = w.pow([m as u64]);
+    let mut result = vec![F::zero(); n];
+    let mut twiddle = vec![F::zero(); r];
+    let mut w_k = F::one();
+    for k in 0..m {
+        let mut w_jk = F::one();
+        for j in 0..r {
+            twiddle[j] = sub_transforms[j][k] * w_jk;
+            w_jk *= w_k;
+        }
+
+        match r {
+            2 => {
+                result[k] = twiddle[0] + twiddle[1];
+                result[k + m] = twiddle[0] - twiddle[1];
+            }
+            4 => {
+                // ζ = w^(n/4) squares to -1, so only one multiplication is needed
+                let (t0, t1, t2, t3) = (twiddle[0], twiddle[1], twiddle[2], twiddle[3]);
+                let (a, b) = (t0 + t2, t0 - t2);
+                let (c, d) = (t1 + t3, (t1 - t3) * zeta);
+                result[k] = a + c;
+                result[k + m] = b + d;
+                result[k + 2 * m] = a - c;
+                result[k + 3 * m] = b - d;
+            }
+            3 => {
+                let zeta_2 = zeta * zeta;
+                let (t0, t1, t2) = (twiddle[0], twiddle[1], twiddle[2]);
+                result[k] = t0 + t1 + t2;
+                result[k + m] = t0 + t1 * zeta + t2 * zeta_2;
+                result[k + 2 * m] = t0 + t1 * zeta_2 + t2 * zeta;
+            }
+            _ => panic!("Unsupported radix {r}"),
+        }
+
+        w_k *= w;
+    }
+
+    result
+}
+
 fn bitreverse(mut n: u32, l: u32) -> u32 {
     let mut r = 0;
     for _ in 0..l {
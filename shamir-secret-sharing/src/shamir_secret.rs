@@ -20,16 +20,14 @@ pub fn create_shares<F: PrimeField>(
         }
     }
 
-    let polynom: SparseUnivariatePolynomial<F> =
-        SparseUnivariatePolynomial::interpolation(&secret_shares);
+    // one subproduct tree evaluation instead of a Horner pass per share
+    let (xs, ys): (Vec<F>, Vec<F>) = secret_shares.into_iter().unzip();
+    let polynom = DenseUnivariatePolynomial::interpolate(ys, xs);
 
-    let mut shares: Vec<(F, F)> = Vec::with_capacity(threshold);
-    for i in 1..=total_shares {
-        let evaluation = SparseUnivariatePolynomial::evaluate(&polynom, F::from(i as u64));
-        shares.push((F::from(i as u64), evaluation));
-    }
+    let indices: Vec<F> = (1..=total_shares).map(|i| F::from(i as u64)).collect();
+    let evaluations = polynom.evaluate_many(&indices);
 
-    shares
+    indices.into_iter().zip(evaluations).collect()
 }
 
 pub fn reconstruct_secret<F: PrimeField>(shares: &[(F, F)], point: F) -> F {
@@ -78,6 +76,21 @@ mod tests {
         // println!("{}", Fr::summary());
     }
 
+    #[test]
+    fn test_create_shares_and_reconstruct_secret_with_many_shares() {
+        let secret = Fr::from(987654321);
+        let threshold = 300;
+        let total_shares = 1000;
+
+        let shares = create_shares(secret, threshold, total_shares);
+        assert_eq!(shares.len(), total_shares);
+
+        let picked_points = shares[total_shares - threshold..].to_vec();
+        let reconstructed_secret = reconstruct_secret(&picked_points, Fr::from(0));
+
+        assert_eq!(secret, reconstructed_secret);
+    }
+
     #[test]
     #[ignore = "reconstruct secret above threshold is failing, will fix later"]
     fn test_create_shares_and_reconstruct_secret_fail_with_points_above_and_below_threshold() {
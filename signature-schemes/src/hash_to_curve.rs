@@ -0,0 +1,126 @@
+use ark_bls12_381::{g1, g2, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::hashing::{
+    curve_maps::wb::WBMap, map_to_curve_hasher::MapToCurveBasedHasher, HashToCurve,
+};
+use ark_ff::field_hashers::DefaultFieldHasher;
+use sha2::Sha256;
+
+use crate::interface::HashToCurveError;
+
+// Hash-to-curve for BLS12-381 following RFC 9380, suites
+// BLS12381G1_XMD:SHA-256_SSWU_RO_ and BLS12381G2_XMD:SHA-256_SSWU_RO_:
+//
+// 1. u0, u1 = hash_to_field(msg, 2)     expand_message_xmd with SHA-256 and the DST
+// 2. Q0, Q1 = map_to_curve(u0), map_to_curve(u1)
+//             simplified SWU on an isogenous curve, then the isogeny back to E
+// 3. P = clear_cofactor(Q0 + Q1)
+//
+// The domain separation tag (DST) must be unique per protocol and use, e.g.
+// "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_" for BLS signatures.
+pub type G1Hasher =
+    MapToCurveBasedHasher<G1Projective, DefaultFieldHasher<Sha256, 128>, WBMap<g1::Config>>;
+pub type G2Hasher =
+    MapToCurveBasedHasher<G2Projective, DefaultFieldHasher<Sha256, 128>, WBMap<g2::Config>>;
+
+/// Hashes `msg` to a point of the prime order subgroup of G1
+pub fn hash_to_g1(dst: &[u8], msg: &[u8]) -> Result<G1Affine, HashToCurveError> {
+    hash::<G1Projective, G1Hasher>(dst, msg)
+}
+
+/// Hashes `msg` to a point of the prime order subgroup of G2
+pub fn hash_to_g2(dst: &[u8], msg: &[u8]) -> Result<G2Affine, HashToCurveError> {
+    hash::<G2Projective, G2Hasher>(dst, msg)
+}
+
+fn hash<C, H>(dst: &[u8], msg: &[u8]) -> Result<C::Affine, HashToCurveError>
+where
+    C: ark_ec::CurveGroup,
+    H: HashToCurve<C>,
+{
+    // RFC 9380 section 3.1: tags longer than 255 bytes have to be hashed down first
+    if dst.is_empty() || dst.len() > 255 {
+        return Err(HashToCurveError::InvalidDomain(
+            "DST should be between 1 and 255 bytes".to_owned(),
+        ));
+    }
+
+    let hasher = H::new(dst).map_err(|e| HashToCurveError::MappingFailed(format!("{:?}", e)))?;
+    hasher
+        .hash(msg)
+        .map_err(|e| HashToCurveError::MappingFailed(format!("{:?}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::{Fq, Fq2};
+    use ark_ff::PrimeField;
+
+    fn fq(hex: &str) -> Fq {
+        let bytes: Vec<u8> = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect();
+        Fq::from_be_bytes_mod_order(&bytes)
+    }
+
+    // RFC 9380 appendix J.9.1, msg = "abc"
+    #[test]
+    fn test_g1_test_vector() {
+        let dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+        let point = hash_to_g1(dst, b"abc").unwrap();
+
+        let expected = G1Affine::new(
+            fq("03567bc5ef9c690c2ab2ecdf6a96ef1c139cc0b2f284dca0a9a7943388a49a3aee664ba5379a7655d3c68900be2f6903"),
+            fq("0b9c15f3fe6e5cf4211f346271d7b01c8f3b28be689c8429c85b67af215533311f0b8dfaaa154fa6b88176c229f2885d"),
+        );
+        assert_eq!(point, expected);
+    }
+
+    // RFC 9380 appendix J.10.1, msg = "abc"
+    #[test]
+    fn test_g2_test_vector() {
+        let dst = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+        let point = hash_to_g2(dst, b"abc").unwrap();
+
+        let expected = G2Affine::new(
+            Fq2::new(
+                fq("02c2d18e033b960562aae3cab37a27ce00d80ccd5ba4b7fe0e7a210245129dbec7780ccc7954725f4168aff2787776e6"),
+                fq("139cddbccdc5e91b9623efd38c49f81a6f83f175e80b06fc374de9eb4b41dfe4ca3a230ed250fbe3a2acf73a41177fd8"),
+            ),
+            Fq2::new(
+                fq("1787327b68159716a37440985269cf584bcb1e621d3a7202be6ea05c4cfe244aeb197642555a0645fb87bf7466b2ba48"),
+                fq("00aa65dae3c8d732d10ecd2c50f8a1baf3001578f71c694e03866e9f3d49ac1e1ce70dd94a733534f106d4cec0eddd16"),
+            ),
+        );
+        assert_eq!(point, expected);
+    }
+
+    #[test]
+    fn test_points_are_in_the_subgroup() {
+        let dst = b"ZK-CRYPTOGRAPHY-TEST-DST";
+        for msg in [&b""[..], b"abc", b"a longer message to hash", &[0u8; 200]] {
+            let p = hash_to_g1(dst, msg).unwrap();
+            assert!(p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve());
+
+            let q = hash_to_g2(dst, msg).unwrap();
+            assert!(q.is_on_curve() && q.is_in_correct_subgroup_assuming_on_curve());
+        }
+    }
+
+    #[test]
+    fn test_domain_separation() {
+        let a = hash_to_g1(b"DOMAIN-A", b"message").unwrap();
+        let b = hash_to_g1(b"DOMAIN-B", b"message").unwrap();
+        assert_ne!(a, b);
+        assert_eq!(a, hash_to_g1(b"DOMAIN-A", b"message").unwrap());
+
+        assert_eq!(
+            hash_to_g1(b"", b"message"),
+            Err(HashToCurveError::InvalidDomain(
+                "DST should be between 1 and 255 bytes".to_owned()
+            ))
+        );
+        assert!(hash_to_g2(&[1u8; 256], b"message").is_err());
+    }
+}
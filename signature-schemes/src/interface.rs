@@ -9,6 +9,12 @@ pub enum SchnorrError {
     InvalidSignature(String),
 }
 
+#[derive(Debug, PartialEq)]
+pub enum HashToCurveError {
+    InvalidDomain(String),
+    MappingFailed(String),
+}
+
 #[derive(Debug)]
 pub enum RSAError {
     BadArgument(String),
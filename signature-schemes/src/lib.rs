@@ -1,3 +1,4 @@
+pub mod hash_to_curve;
 pub mod interface;
 pub mod rsa;
 pub mod schnorr;
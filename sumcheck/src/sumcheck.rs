@@ -98,9 +98,11 @@ impl<F: PrimeField> Sumcheck<F> {
 #[cfg(test)]
 mod tests {
     use ark_test_curves::bls12_381::Fr as Fr_old;
+    use field::small_prime_field::SmallPrimeField;
     use field_tracker::Ft;
 
     use super::*;
+    use polynomial::VariableOrder;
 
     type Fr = Ft<4, Fr_old>;
 
@@ -200,4 +202,44 @@ mod tests {
         assert_eq!(verifer, true);
         // println!("{}", Fr::summary());
     }
+
+    #[test]
+    fn test_sum_check_exhaustive_small_field() {
+        type F5 = SmallPrimeField<5>;
+
+        // every 2-variate multilinear polynomial over F_5
+        for index in 0..5u64.pow(4) {
+            let evaluations = (0..4).map(|i| F5::from(index / 5u64.pow(i) % 5)).collect();
+            let mut sumcheck = Sumcheck::new(Multilinear::new(evaluations));
+            sumcheck.poly_sum();
+            let (proof, _) = sumcheck.prove();
+            assert!(sumcheck.verify(&proof));
+
+            // a wrong claimed sum is always caught in the first round
+            sumcheck.sum += F5::from(1);
+            let (proof, _) = sumcheck.prove();
+            assert!(!sumcheck.verify(&proof));
+        }
+    }
+
+    #[test]
+    fn test_sum_check_under_both_variable_orders() {
+        let evaluations: Vec<Fr> = (0..16u64).map(|i| Fr::from(i * i + 3)).collect();
+        let msb = Multilinear::new(evaluations);
+        let lsb = msb.to_order(VariableOrder::LsbFirst);
+
+        let mut msb_sumcheck = Sumcheck::new(msb);
+        msb_sumcheck.poly_sum();
+        let (msb_proof, msb_challenges) = msb_sumcheck.prove();
+
+        let mut lsb_sumcheck = Sumcheck::new(lsb);
+        lsb_sumcheck.poly_sum();
+        let (lsb_proof, lsb_challenges) = lsb_sumcheck.prove();
+
+        // the same polynomial gives the same transcript whatever the layout
+        assert!(lsb_sumcheck.verify(&lsb_proof));
+        assert_eq!(lsb_sumcheck.sum, msb_sumcheck.sum);
+        assert_eq!(lsb_proof.univariate_poly, msb_proof.univariate_poly);
+        assert_eq!(lsb_challenges, msb_challenges);
+    }
 }